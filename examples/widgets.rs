@@ -1,5 +1,6 @@
 use coat::backend::winit::{AppEvent, Window};
 use coat::*;
+use std::time::Duration;
 
 fn main() {
     env_logger::init();
@@ -99,6 +100,17 @@ impl<'a> Button<'a> {
 struct ButtonState {
     hovered: bool,
     pressed: bool,
+    background: Animated<Color>,
+}
+
+impl ButtonState {
+    fn background(&self) -> Color {
+        match () {
+            () if self.pressed => Color::rgb(0.2, 0.2, 0.6),
+            () if self.hovered => Color::rgb(0.4, 0.4, 0.8),
+            () => Color::rgb(0.3, 0.3, 0.7),
+        }
+    }
 }
 
 enum ButtonMsg {
@@ -117,9 +129,12 @@ impl<'a> Component for Button<'a> {
     type Event = ButtonEvent;
 
     fn init(_props: &Self) -> Self::State {
+        let background = Color::rgb(0.3, 0.3, 0.7);
         ButtonState {
             hovered: false,
             pressed: false,
+            background: Animated::new(background)
+                .tween(Duration::from_millis(150), Easing::EaseOut),
         }
     }
 
@@ -139,14 +154,21 @@ impl<'a> Component for Button<'a> {
                 }
             }
         }
+
+        let background = state.background();
+        if background != state.background.target() {
+            state.background.animate_to(background, ui);
+        }
+    }
+
+    fn tick(mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.background.drive(ui);
     }
 
     fn view(props: &Self, state: &Self::State, ui: &mut UiView<Self>) {
         let background = match () {
             () if !props.enabled => Color::rgb(0.3, 0.3, 0.3),
-            () if state.pressed => Color::rgb(0.2, 0.2, 0.6),
-            () if state.hovered => Color::rgb(0.4, 0.4, 0.8),
-            () => Color::rgb(0.3, 0.3, 0.7),
+            () => state.background.value(),
         };
 
         TouchArea::new()
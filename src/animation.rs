@@ -0,0 +1,11 @@
+mod animatable;
+pub use self::animatable::Animatable;
+
+mod easing;
+pub use self::easing::Easing;
+
+mod spring;
+pub use self::spring::Spring;
+
+mod animated;
+pub use self::animated::{Animated, Transition};
@@ -0,0 +1,125 @@
+use crate::{Color, Position, Scalar, Size};
+
+/// A value that can be interpolated and moved by a `Spring`.
+///
+/// Think of it as a vector, all operations are applied component-wise.
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn scale(self, factor: Scalar) -> Self;
+    /// The largest absolute component, used to detect when a spring is at rest.
+    fn magnitude(self) -> Scalar;
+
+    /// Interpolates between `self` and `to`, where `t` is usually in `0.0..=1.0`.
+    fn lerp(self, to: Self, t: Scalar) -> Self {
+        self.add(to.sub(self).scale(t))
+    }
+}
+
+impl Animatable for Scalar {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    fn scale(self, factor: Scalar) -> Self {
+        self * factor
+    }
+
+    fn magnitude(self) -> Scalar {
+        self.abs()
+    }
+}
+
+impl Animatable for Color {
+    fn zero() -> Self {
+        Color::rgba(0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn add(self, other: Self) -> Self {
+        Color::rgba(
+            self.r + other.r,
+            self.g + other.g,
+            self.b + other.b,
+            self.a + other.a,
+        )
+    }
+
+    fn sub(self, other: Self) -> Self {
+        Color::rgba(
+            self.r - other.r,
+            self.g - other.g,
+            self.b - other.b,
+            self.a - other.a,
+        )
+    }
+
+    fn scale(self, factor: Scalar) -> Self {
+        Color::rgba(
+            self.r * factor,
+            self.g * factor,
+            self.b * factor,
+            self.a * factor,
+        )
+    }
+
+    fn magnitude(self) -> Scalar {
+        self.r
+            .abs()
+            .max(self.g.abs())
+            .max(self.b.abs())
+            .max(self.a.abs())
+    }
+}
+
+impl Animatable for Position {
+    fn zero() -> Self {
+        Position::zero()
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other.to_vector()
+    }
+
+    fn sub(self, other: Self) -> Self {
+        (self - other).to_point()
+    }
+
+    fn scale(self, factor: Scalar) -> Self {
+        self * factor
+    }
+
+    fn magnitude(self) -> Scalar {
+        self.x.abs().max(self.y.abs())
+    }
+}
+
+impl Animatable for Size {
+    fn zero() -> Self {
+        Size::zero()
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    fn scale(self, factor: Scalar) -> Self {
+        self * factor
+    }
+
+    fn magnitude(self) -> Scalar {
+        self.width.abs().max(self.height.abs())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::animation::{Animatable, Easing, Spring};
+use crate::{Scalar, UiUpdate};
+use std::time::{Duration, Instant};
+
+/// How an `Animated` value moves towards its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    Tween { duration: Duration, easing: Easing },
+    Spring(Spring),
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition::Tween {
+            duration: Duration::from_millis(200),
+            easing: Easing::default(),
+        }
+    }
+}
+
+/// Springs are at rest once both their displacement and velocity are below this.
+const REST_THRESHOLD: Scalar = 0.001;
+/// Long pauses between two frames would make springs jump.
+const MAX_FRAME_TIME: Scalar = 0.064;
+
+/// A value that animates towards its target whenever it changes.
+///
+/// It is meant to be stored in the `State` of a component: `animate_to` starts the
+/// animation from `update` and `drive` advances it from `Component::tick`, which the
+/// event loop calls on every frame until the target is reached.
+/// The current value is available through `value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animated<T: Animatable> {
+    value: T,
+    target: T,
+    from: T,
+    velocity: T,
+    transition: Transition,
+    started: Instant,
+    last_tick: Instant,
+    animating: bool,
+}
+
+impl<T: Animatable> Animated<T> {
+    pub fn new(value: T) -> Self {
+        let now = Instant::now();
+        Animated {
+            value,
+            target: value,
+            from: value,
+            velocity: T::zero(),
+            transition: Transition::default(),
+            started: now,
+            last_tick: now,
+            animating: false,
+        }
+    }
+
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Changes the transition, a running animation continues with the new one.
+    pub fn set_transition(&mut self, transition: Transition) {
+        self.transition = transition;
+    }
+
+    pub fn tween(self, duration: Duration, easing: Easing) -> Self {
+        self.transition(Transition::Tween { duration, easing })
+    }
+
+    pub fn spring(self, spring: Spring) -> Self {
+        self.transition(Transition::Spring(spring))
+    }
+
+    /// The current value.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// The value that is being animated towards.
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    /// Starts animating towards `target` at `now`, does nothing if it is the target already.
+    ///
+    /// A running spring keeps its velocity, a tween starts over from the current value.
+    pub fn set(&mut self, target: T, now: Instant) {
+        if target == self.target {
+            return;
+        }
+        self.target = target;
+        self.from = self.value;
+        self.started = now;
+        self.last_tick = now;
+        self.animating = true;
+    }
+
+    /// Sets the value immediately, without animating.
+    pub fn jump(&mut self, value: T) {
+        self.value = value;
+        self.target = value;
+        self.from = value;
+        self.velocity = T::zero();
+        self.animating = false;
+    }
+
+    /// Advances the animation to `now`, returns whether it is still running.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if !self.animating {
+            return false;
+        }
+
+        match self.transition {
+            Transition::Tween { duration, easing } => {
+                let elapsed = now.saturating_duration_since(self.started).as_secs_f32();
+                let progress = match duration.as_secs_f32() {
+                    total if total > 0.0 => elapsed / total,
+                    _ => 1.0,
+                };
+                self.value = self.from.lerp(self.target, easing.apply(progress));
+                self.animating = progress < 1.0;
+            }
+            Transition::Spring(spring) => {
+                let elapsed = now.saturating_duration_since(self.last_tick).as_secs_f32();
+                let elapsed = elapsed.min(MAX_FRAME_TIME);
+                spring.step(&mut self.value, &mut self.velocity, self.target, elapsed);
+                let displacement = self.value.sub(self.target).magnitude();
+                self.animating =
+                    displacement > REST_THRESHOLD || self.velocity.magnitude() > REST_THRESHOLD;
+            }
+        }
+        self.last_tick = now;
+
+        if !self.animating {
+            self.jump(self.target);
+        }
+        self.animating
+    }
+
+    /// Starts animating towards `target` and requests a tick of the component.
+    pub fn animate_to(&mut self, target: T, ui: &mut UiUpdate) {
+        self.set(target, ui.frame_time());
+        if self.animating {
+            ui.request_tick();
+        }
+    }
+
+    /// Advances the animation to the current frame and requests
+    /// another tick while it is still running.
+    pub fn drive(&mut self, ui: &mut UiUpdate) {
+        if self.tick(ui.frame_time()) {
+            ui.request_tick();
+        }
+    }
+}
+
+impl<T: Animatable + Default> Default for Animated<T> {
+    fn default() -> Self {
+        Animated::new(T::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tween() {
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        let mut value = Animated::new(0.0).tween(second, Easing::Linear);
+
+        value.set(10.0, start);
+        assert!(value.tick(start + second / 2));
+        assert!((value.value() - 5.0).abs() < 1e-3);
+
+        assert!(!value.tick(start + second));
+        assert_eq!(value.value(), 10.0);
+        assert!(!value.is_animating());
+    }
+
+    #[test]
+    fn test_spring_settles() {
+        let mut now = Instant::now();
+        let frame = Duration::from_millis(16);
+        let mut value = Animated::new(0.0).spring(Spring::wobbly());
+
+        value.set(1.0, now);
+        let mut overshot = false;
+        for _ in 0..1000 {
+            now += frame;
+            if !value.tick(now) {
+                break;
+            }
+            overshot |= value.value() > 1.0;
+        }
+
+        assert!(overshot);
+        assert!(!value.is_animating());
+        assert_eq!(value.value(), 1.0);
+    }
+
+    #[test]
+    fn test_stiff_spring_does_not_overshoot() {
+        let mut now = Instant::now();
+        let frame = Duration::from_millis(16);
+        let mut value = Animated::new(0.0).spring(Spring::stiff());
+
+        value.set(1.0, now);
+        while value.tick(now) {
+            now += frame;
+            assert!(value.value() <= 1.0);
+        }
+        assert_eq!(value.value(), 1.0);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::Scalar;
+
+/// Maps the linear progress of a tween onto a curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// A CSS like cubic bezier curve through `(0, 0)`, `(x1, y1)`, `(x2, y2)` and `(1, 1)`.
+    CubicBezier(Scalar, Scalar, Scalar, Scalar),
+}
+
+// Written out like the other defaults of the crate
+#[allow(clippy::derivable_impls)]
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseInOut
+    }
+}
+
+impl Easing {
+    /// Applies the curve to `t`, which gets clamped to `0.0..=1.0`.
+    pub fn apply(self, t: Scalar) -> Scalar {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+fn bezier(p1: Scalar, p2: Scalar, s: Scalar) -> Scalar {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+/// Finds the `s` for which the curve passes `x` by bisection and returns its `y`.
+fn cubic_bezier(x1: Scalar, y1: Scalar, x2: Scalar, y2: Scalar, x: Scalar) -> Scalar {
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut s = x;
+    for _ in 0..32 {
+        let current = bezier(x1, x2, s);
+        if (current - x).abs() < 1e-5 {
+            break;
+        }
+        if current < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_endpoints() {
+        let easings = [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+        ];
+        for easing in &easings {
+            assert!(easing.apply(0.0).abs() < 1e-4, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4, "{:?}", easing);
+            assert!((easing.apply(2.0) - 1.0).abs() < 1e-4, "{:?}", easing);
+        }
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-4);
+        assert!((Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.3) - 0.3).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::animation::Animatable;
+use crate::Scalar;
+
+/// A damped spring pulling a value towards its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    pub stiffness: Scalar,
+    pub damping: Scalar,
+    pub mass: Scalar,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Spring {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+        }
+    }
+}
+
+impl Spring {
+    /// A spring that barely overshoots.
+    pub fn gentle() -> Self {
+        Spring {
+            stiffness: 120.0,
+            damping: 14.0,
+            mass: 1.0,
+        }
+    }
+
+    /// A spring that noticeably overshoots before it settles.
+    pub fn wobbly() -> Self {
+        Spring {
+            stiffness: 180.0,
+            damping: 12.0,
+            mass: 1.0,
+        }
+    }
+
+    /// A fast spring without any overshoot.
+    ///
+    /// Its damping is slightly above `2 * sqrt(stiffness * mass)`, the critical damping.
+    pub fn stiff() -> Self {
+        Spring {
+            stiffness: 210.0,
+            damping: 30.0,
+            mass: 1.0,
+        }
+    }
+
+    /// Advances `value` and `velocity` by `seconds` towards `target`.
+    pub(crate) fn step<T: Animatable>(
+        self,
+        value: &mut T,
+        velocity: &mut T,
+        target: T,
+        seconds: Scalar,
+    ) {
+        // Small fixed steps keep stiff springs stable
+        const STEP: Scalar = 1.0 / 1000.0;
+        let mut remaining = seconds;
+        while remaining > 0.0 {
+            let dt = remaining.min(STEP);
+            let displacement = value.sub(target);
+            let force = displacement
+                .scale(-self.stiffness)
+                .sub(velocity.scale(self.damping));
+            *velocity = velocity.add(force.scale(dt / self.mass));
+            *value = value.add(velocity.scale(dt));
+            remaining -= dt;
+        }
+    }
+}
@@ -1,10 +1,52 @@
 //! Provides `struct`s and `trait`s that are the same for any back end
 //! as well as back end specific code.
 //!
-//! Independent of the back end it should export `Renderer`
+//! Independent of the back end it should implement `Renderer`
 
+use crate::{Bitmap, Bounds, Color, Font, FontSize, LayoutGlyph};
+
+pub mod headless;
+pub mod software;
 pub mod webrender;
 pub mod winit;
 
-/// Contains all data and functions required to render something with the chosen back end
-pub type Renderer = self::webrender::Webrenderer;
+mod font_manager;
+pub use self::font_manager::FontManager;
+
+pub(crate) static DEFAULT_FONT: &[u8] = include_bytes!("../assets/fonts/OpenSans-Regular.ttf");
+pub(crate) static DEFAULT_FONT_NAME: &str = "OpenSans";
+
+/// Contains all data and functions required to render something with the chosen back end.
+///
+/// This is what `Component::render` draws with, the positions
+/// of all primitives are absolute. No widget should ever need to know
+/// which back end it is drawing with.
+pub trait Renderer {
+    /// The fonts known to this renderer, they are also used to layout text.
+    fn fonts(&self) -> &FontManager;
+
+    fn add_font(&mut self, font: Font, data: Vec<u8>);
+
+    fn remove_font(&mut self, font: &Font);
+
+    /// Fills `bounds` with `color`.
+    fn rect(&mut self, bounds: Bounds, color: Color);
+
+    /// Draws a run of glyphs, their positions are relative to the origin of `bounds`.
+    fn glyphs(
+        &mut self,
+        bounds: Bounds,
+        font: &Font,
+        size: FontSize,
+        color: Color,
+        glyphs: &[LayoutGlyph],
+    );
+
+    /// Draws `bitmap` stretched to `bounds`.
+    fn image(&mut self, bounds: Bounds, bitmap: &Bitmap);
+
+    /// Restricts everything that is drawn until the matching `pop_clip` to `bounds`.
+    fn push_clip(&mut self, bounds: Bounds);
+
+    fn pop_clip(&mut self);
+}
@@ -0,0 +1,97 @@
+use crate::backend::DEFAULT_FONT_NAME;
+use crate::{Bounds, Font, FontSize, LayoutGlyph, Position, Scalar, Size, TextLayout};
+use fnv::FnvHashMap as HashMap;
+
+/// Keeps track of all loaded fonts and lays out text with them.
+pub struct FontManager {
+    fonts: HashMap<Font, rusttype::Font<'static>>,
+    default_font: Font,
+}
+
+impl Default for FontManager {
+    fn default() -> Self {
+        FontManager {
+            fonts: HashMap::default(),
+            default_font: Font::from_family(DEFAULT_FONT_NAME),
+        }
+    }
+}
+
+impl FontManager {
+    pub(crate) fn add_font(&mut self, font: Font, data: Vec<u8>) {
+        let rt_font = rusttype::Font::from_bytes(data).unwrap();
+        self.fonts.insert(font, rt_font);
+    }
+
+    pub(crate) fn remove_font(&mut self, font: &Font) {
+        if self.fonts.remove(font).is_none() {
+            log::error!("Tried to remove unknown {:?}", font);
+        }
+    }
+
+    pub fn contains(&self, font: &Font) -> bool {
+        self.fonts.contains_key(font)
+    }
+
+    pub fn default_font(&self) -> &Font {
+        &self.default_font
+    }
+
+    pub fn rusttype(&self, font: &Font) -> &rusttype::Font<'static> {
+        &self.fonts[font]
+    }
+
+    /// The scale glyphs of `size` are laid out and have to be rendered with.
+    pub(crate) fn scale(size: FontSize) -> rusttype::Scale {
+        let size = size as f32;
+        rusttype::Scale {
+            // TODO: Fix glyph overlapping without additional x-scaling
+            // The current value roughly fits OpenSans
+            x: size * 1.2,
+            y: size,
+        }
+    }
+
+    pub fn layout(&self, text: &str, font: Option<&Font>, size: FontSize) -> TextLayout {
+        let scale = FontManager::scale(size);
+        let size = size as f32;
+        let font = &self.fonts[font.unwrap_or(self.default_font())];
+        let vmetrics = font.v_metrics(scale);
+        // NOTE: The descent offset compensates for ignoring the baseline
+        let point = rusttype::Point {
+            x: 0.0,
+            y: vmetrics.descent,
+        };
+        let glyphs: Vec<LayoutGlyph> = font
+            .layout(text, scale, point)
+            .map(|glyph| {
+                let index = glyph.id().0;
+                let pos = glyph.position();
+                let height = glyph.scale().y;
+                let width = glyph.unpositioned().h_metrics().advance_width;
+                let point = Position::new(pos.x, pos.y + size);
+                let size = Size::new(width, height);
+                LayoutGlyph {
+                    index,
+                    bounds: Bounds::new(point, size),
+                }
+            })
+            .collect();
+
+        // NOTE: The glyphs are moved down by `size` above, their baseline lies at `point.y`
+        let baseline = (size + vmetrics.descent) as Scalar;
+
+        // NOTE: The descent is usually negative
+        let height = (vmetrics.descent.abs() + vmetrics.ascent.abs()) as Scalar;
+        let size = glyphs
+            .last()
+            .map(|glyph| Size::new(glyph.bounds.max_x(), height))
+            .unwrap_or(Size::new(0.0, height));
+
+        TextLayout {
+            size,
+            baseline,
+            glyphs,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Runs the whole ui pipeline without a window or GPU.
+//!
+//! By default everything that is being drawn gets recorded into
+//! a `DisplayList` which can be inspected afterwards, but any
+//! `HeadlessRenderer` like the `SoftwareRenderer` can be used instead.
+
+use crate::backend::{DEFAULT_FONT, DEFAULT_FONT_NAME};
+use crate::{
+    Cid, Component, Event, Font, Input, Renderer, Size, UiData, UiInput, UiLayout, UiRender,
+    UiUpdate, UiView,
+};
+use std::marker::PhantomData;
+use std::time::Instant;
+
+mod display_list;
+pub use self::display_list::{DisplayItem, DisplayList};
+
+mod snapshot;
+pub use self::snapshot::assert_snapshot;
+
+mod driver;
+
+/// A `Renderer` that can be driven by `Headless`.
+pub trait HeadlessRenderer: Renderer + Default {
+    /// Discards the previous frame, this is called before everything gets rendered again.
+    fn begin_frame(&mut self, size: Size);
+}
+
+/// Drives a root `Component` like a `Window` would, but on demand.
+///
+/// ```ignore
+/// let mut app = Headless::<App>::new(Size::new(600.0, 400.0));
+/// app.run();
+/// app.push_event(Event::Cursor(Position::new(10.0, 10.0)));
+/// app.run();
+/// assert!(!app.display_list().items().is_empty());
+/// ```
+///
+/// For tests there are helpers like `click`, `type_text` and `press`
+/// which simulate a user and `settle` the ui afterwards.
+pub struct Headless<Comp: Component, R: HeadlessRenderer = DisplayList> {
+    data: UiData,
+    input: Input,
+    renderer: R,
+    root: Cid,
+    size: Size,
+    resized: bool,
+    /// Whether messages have been sent to the root since the last `run`
+    sent: bool,
+    marker: PhantomData<Comp>,
+}
+
+impl<Comp: Component, R: HeadlessRenderer> Headless<Comp, R> {
+    pub fn new(size: Size) -> Self {
+        let mut data = UiData::default();
+        let root = data.fresh_id();
+        let mut renderer = R::default();
+        renderer.add_font(Font::from_family(DEFAULT_FONT_NAME), DEFAULT_FONT.to_vec());
+
+        Headless {
+            data,
+            input: Input::new(),
+            renderer,
+            root,
+            size,
+            resized: true,
+            sent: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Queues an `Event` which will be handled during the next `run`.
+    pub fn push_event(&mut self, event: Event) {
+        self.input.push_event(event);
+    }
+
+    pub fn resize(&mut self, size: Size) {
+        self.size = size;
+        self.resized = true;
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Runs the pipeline once, just like a `Window` does for every frame.
+    /// Returns whether or not the ui has been rendered again.
+    pub fn run(&mut self) -> bool {
+        let root = self.root;
+        let resized = self.resized;
+        let mut rendered = false;
+
+        let delivered = std::mem::take(&mut self.sent)
+            | self.data.begin_frame(Instant::now())
+            | self.data.poll_tasks()
+            | self.data.poll_timers();
+        let needs_update =
+            UiInput::<Comp>::run(&mut self.data, &mut self.input, root) || delivered || resized;
+        let updated = needs_update && UiUpdate::run(&mut self.data, &mut self.renderer, root);
+        if updated || resized {
+            UiView::<Comp>::run(&mut self.data, &mut self.renderer, root, Comp::default());
+            UiLayout::run(&mut self.data, root, self.size);
+            self.renderer.begin_frame(self.size);
+            UiRender::run(&self.data, &mut self.renderer, root);
+            rendered = true;
+        }
+
+        self.resized = false;
+        self.input.clear_events();
+        rendered
+    }
+
+    /// The `Renderer` holding the result of the last render.
+    pub fn renderer(&self) -> &R {
+        &self.renderer
+    }
+
+    pub fn renderer_mut(&mut self) -> &mut R {
+        &mut self.renderer
+    }
+}
+
+impl<Comp: Component> Headless<Comp, DisplayList> {
+    /// Everything that has been drawn during the last render.
+    pub fn display_list(&self) -> &DisplayList {
+        &self.renderer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+
+    #[derive(Default)]
+    struct App;
+
+    impl Component for App {
+        type State = ();
+        type Msg = ();
+        type Event = ();
+
+        fn init(_props: &Self) -> Self::State {}
+
+        fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+            Padding::new().all(10.0).set(iid!(), ui).add(|| {
+                Rectangle::new()
+                    .color(Color::rgb(1.0, 0.0, 0.0))
+                    .set(iid!(), ui);
+            });
+        }
+    }
+
+    #[test]
+    fn test_records_display_list() {
+        let mut app: Headless<App> = Headless::new(Size::new(100.0, 50.0));
+        assert!(app.run());
+
+        let expected = DisplayItem::Rect {
+            bounds: Bounds::new(Position::new(10.0, 10.0), Size::new(80.0, 30.0)),
+            color: Color::rgb(1.0, 0.0, 0.0),
+        };
+        assert_eq!(app.display_list().items(), &[expected]);
+
+        assert!(!app.run());
+    }
+}
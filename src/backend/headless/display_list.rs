@@ -0,0 +1,96 @@
+use crate::backend::headless::HeadlessRenderer;
+use crate::{Bitmap, Bounds, Color, Font, FontManager, FontSize, LayoutGlyph, Renderer, Size};
+
+/// A single primitive that has been drawn by a `Component`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayItem {
+    Rect {
+        bounds: Bounds,
+        color: Color,
+    },
+    Glyphs {
+        bounds: Bounds,
+        font: Font,
+        size: FontSize,
+        color: Color,
+        glyphs: Vec<LayoutGlyph>,
+    },
+    Image {
+        bounds: Bounds,
+        bitmap: Bitmap,
+    },
+    PushClip {
+        bounds: Bounds,
+    },
+    PopClip,
+}
+
+/// A `Renderer` that only records what is being drawn.
+#[derive(Default)]
+pub struct DisplayList {
+    fonts: FontManager,
+    items: Vec<DisplayItem>,
+}
+
+impl DisplayList {
+    /// All primitives in the order they have been drawn.
+    pub fn items(&self) -> &[DisplayItem] {
+        &self.items
+    }
+}
+
+impl HeadlessRenderer for DisplayList {
+    fn begin_frame(&mut self, _size: Size) {
+        self.items.clear();
+    }
+}
+
+impl Renderer for DisplayList {
+    fn fonts(&self) -> &FontManager {
+        &self.fonts
+    }
+
+    fn add_font(&mut self, font: Font, data: Vec<u8>) {
+        self.fonts.add_font(font, data);
+    }
+
+    fn remove_font(&mut self, font: &Font) {
+        self.fonts.remove_font(font);
+    }
+
+    fn rect(&mut self, bounds: Bounds, color: Color) {
+        self.items.push(DisplayItem::Rect { bounds, color });
+    }
+
+    fn glyphs(
+        &mut self,
+        bounds: Bounds,
+        font: &Font,
+        size: FontSize,
+        color: Color,
+        glyphs: &[LayoutGlyph],
+    ) {
+        self.items.push(DisplayItem::Glyphs {
+            bounds,
+            font: font.clone(),
+            size,
+            color,
+            glyphs: glyphs.to_vec(),
+        });
+    }
+
+    fn image(&mut self, bounds: Bounds, bitmap: &Bitmap) {
+        self.items.push(DisplayItem::Image {
+            bounds,
+            bitmap: bitmap.clone(),
+        });
+    }
+
+    fn push_clip(&mut self, bounds: Bounds) {
+        self.items.push(DisplayItem::PushClip { bounds });
+    }
+
+    fn pop_clip(&mut self) {
+        self.items.push(DisplayItem::PopClip);
+    }
+}
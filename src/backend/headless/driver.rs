@@ -0,0 +1,98 @@
+//! Helpers to simulate a user in tests.
+
+use crate::backend::headless::{Headless, HeadlessRenderer};
+use crate::{
+    ButtonState, Component, Event, KeyboardEvent, ModifiersState, MouseButton, MouseEvent,
+    Position, Query, VirtualKeyCode,
+};
+
+/// `settle` gives up after this many runs, as the ui will most likely never settle then.
+const MAX_SETTLE_RUNS: usize = 100;
+
+impl<Comp: Component, R: HeadlessRenderer> Headless<Comp, R> {
+    /// Runs the pipeline until nothing changes anymore.
+    /// Returns whether the ui has been rendered again.
+    pub fn settle(&mut self) -> bool {
+        let mut rendered = false;
+        for _ in 0..MAX_SETTLE_RUNS {
+            if !self.run() {
+                return rendered;
+            }
+            rendered = true;
+        }
+        log::error!("The ui did not settle after {} runs", MAX_SETTLE_RUNS);
+        rendered
+    }
+
+    /// Queues `event` and settles the ui.
+    pub fn event(&mut self, event: Event) {
+        self.push_event(event);
+        self.settle();
+    }
+
+    /// Moves the cursor to `position`.
+    pub fn hover(&mut self, position: Position) {
+        self.event(Event::Cursor(position));
+    }
+
+    /// Moves the cursor to `position` and presses and releases the left mouse button.
+    pub fn click(&mut self, position: Position) {
+        self.hover(position);
+        for &state in &[ButtonState::Pressed, ButtonState::Released] {
+            self.event(Event::Mouse(MouseEvent {
+                position,
+                button: MouseButton::Left,
+                state,
+            }));
+        }
+    }
+
+    /// Presses and releases `key`.
+    pub fn press(&mut self, key: VirtualKeyCode) {
+        self.press_with(key, ModifiersState::default());
+    }
+
+    /// Presses and releases `key` while holding the `modifiers`.
+    pub fn press_with(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) {
+        for &state in &[ButtonState::Pressed, ButtonState::Released] {
+            self.event(Event::Keyboard(KeyboardEvent {
+                scancode: 0,
+                keycode: Some(key),
+                state,
+                modifiers,
+            }));
+        }
+    }
+
+    /// Types the `text` one character at a time.
+    pub fn type_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.event(Event::Character(ch));
+        }
+    }
+
+    /// Sends `msg` to the root component and settles the ui.
+    pub fn send(&mut self, msg: Comp::Msg) {
+        if self.data.is_fresh(self.root) {
+            self.settle();
+        }
+        self.data.push_message::<Comp>(self.root, msg);
+        self.sent = true;
+        self.settle();
+    }
+
+    /// Takes all events that have been emitted by the root component so far.
+    pub fn events(&mut self) -> Vec<Comp::Event> {
+        self.data.take_events(self.root)
+    }
+
+    /// Looks up components of the last `run`.
+    pub fn query(&self) -> Query {
+        Query::new(&self.data, self.root)
+    }
+
+    /// The state of the root component, `None` until the first `run`.
+    pub fn state(&self) -> Option<&Comp::State> {
+        self.data.state[self.root.get()].as_ref()?.downcast_ref()
+    }
+}
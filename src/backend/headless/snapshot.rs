@@ -0,0 +1,139 @@
+use crate::backend::headless::{DisplayItem, DisplayList};
+use crate::{Bounds, Color, Font, Scalar};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+impl DisplayList {
+    /// Serializes all items into a stable, line based text format.
+    ///
+    /// Every primitive gets its own line and everything that is
+    /// drawn inside of a clip is indented below it.
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::new();
+        let mut depth = 0;
+
+        for item in self.items() {
+            if let DisplayItem::PopClip = item {
+                depth -= 1;
+                continue;
+            }
+
+            for _ in 0..depth {
+                snapshot.push_str("  ");
+            }
+
+            match item {
+                DisplayItem::Rect { bounds, color } => {
+                    write!(snapshot, "rect {} {}", bounds_str(bounds), color_str(color))
+                }
+                DisplayItem::Glyphs {
+                    bounds,
+                    font,
+                    size,
+                    color,
+                    glyphs,
+                } => {
+                    let indices: Vec<String> = glyphs.iter().map(|g| g.index.to_string()).collect();
+                    write!(
+                        snapshot,
+                        "glyphs {} {} {} {} [{}]",
+                        bounds_str(bounds),
+                        color_str(color),
+                        font_str(font),
+                        size,
+                        indices.join(" ")
+                    )
+                }
+                DisplayItem::Image { bounds, bitmap } => write!(
+                    snapshot,
+                    "image {} {}x{}",
+                    bounds_str(bounds),
+                    bitmap.width(),
+                    bitmap.height()
+                ),
+                DisplayItem::PushClip { bounds } => {
+                    depth += 1;
+                    write!(snapshot, "clip {}", bounds_str(bounds))
+                }
+                DisplayItem::PopClip => unreachable!(),
+            }
+            .unwrap();
+            snapshot.push('\n');
+        }
+
+        snapshot
+    }
+}
+
+/// Rounds to two decimal places, so tiny floating point differences do not matter.
+fn round(value: Scalar) -> Scalar {
+    (value * 100.0).round() / 100.0
+}
+
+fn bounds_str(bounds: &Bounds) -> String {
+    format!(
+        "({} {} {} {})",
+        round(bounds.origin.x),
+        round(bounds.origin.y),
+        round(bounds.size.width),
+        round(bounds.size.height)
+    )
+}
+
+fn color_str(color: &Color) -> String {
+    format!(
+        "rgba({} {} {} {})",
+        round(color.r),
+        round(color.g),
+        round(color.b),
+        round(color.a)
+    )
+}
+
+fn font_str(font: &Font) -> String {
+    let style = if font.italic { " italic" } else { "" };
+    format!("\"{}\" {:?}{}", font.family, font.weight, style)
+}
+
+/// Compares `snapshot` with the content of the file at `path`.
+///
+/// On mismatch the new snapshot is written next to it with an
+/// additional `.new` extension and the calling test fails.
+/// Review the difference and replace the old file to accept it.
+pub fn assert_snapshot(path: impl AsRef<Path>, snapshot: &str) {
+    let path = path.as_ref();
+    let expected = std::fs::read_to_string(path).ok();
+    if expected.as_deref() == Some(snapshot) {
+        return;
+    }
+
+    let mut new_path = PathBuf::from(path);
+    let mut extension = new_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned() + ".")
+        .unwrap_or_default();
+    extension.push_str("new");
+    new_path.set_extension(extension);
+    std::fs::write(&new_path, snapshot).unwrap();
+
+    match expected {
+        None => panic!(
+            "There is no snapshot at {}, the current one has been written to {}",
+            path.display(),
+            new_path.display()
+        ),
+        Some(expected) => {
+            let line = expected
+                .lines()
+                .zip(snapshot.lines())
+                .position(|(expected, actual)| expected != actual)
+                .unwrap_or_else(|| expected.lines().count().min(snapshot.lines().count()));
+            panic!(
+                "The snapshot {} differs starting at line {}, the new one has been written to {}",
+                path.display(),
+                line + 1,
+                new_path.display()
+            )
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! A pure Rust back end which rasterizes everything on the CPU
+//! into an in-memory RGBA frame buffer.
+//!
+//! It is mainly meant for screenshot tests and machines without GL.
+
+use crate::backend::headless::HeadlessRenderer;
+use crate::{Bitmap, Bounds, Color, Font, FontManager, FontSize, LayoutGlyph, Renderer, Size};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The color every frame is being cleared with, the same as for webrender.
+const CLEAR_COLOR: Color = Color {
+    r: 0.1,
+    g: 0.1,
+    b: 0.1,
+    a: 1.0,
+};
+
+/// A region of the frame buffer in whole pixels, `max` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PixelRect {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+}
+
+impl PixelRect {
+    fn from_bounds(bounds: Bounds) -> Self {
+        PixelRect {
+            min_x: bounds.min_x().round() as i32,
+            min_y: bounds.min_y().round() as i32,
+            max_x: bounds.max_x().round() as i32,
+            max_y: bounds.max_y().round() as i32,
+        }
+    }
+
+    fn intersection(self, other: PixelRect) -> PixelRect {
+        PixelRect {
+            min_x: self.min_x.max(other.min_x),
+            min_y: self.min_y.max(other.min_y),
+            max_x: self.max_x.min(other.max_x),
+            max_y: self.max_y.min(other.max_y),
+        }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.min_x && x < self.max_x && y >= self.min_y && y < self.max_y
+    }
+}
+
+/// A `Renderer` that draws into a RGBA frame buffer with straight alpha.
+#[derive(Default)]
+pub struct SoftwareRenderer {
+    fonts: FontManager,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    clips: Vec<PixelRect>,
+}
+
+impl SoftwareRenderer {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The frame buffer, row by row with four bytes per pixel.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The RGBA value of a single pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = (y * self.width + x) as usize * 4;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[index..index + 4]);
+        pixel
+    }
+
+    /// Copies the current frame into a `Bitmap`.
+    pub fn to_bitmap(&self) -> Bitmap {
+        Bitmap::from_rgba(self.width, self.height, self.pixels.clone())
+    }
+
+    /// Writes the current frame to a PNG file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        use png::HasParameters;
+
+        let file = std::fs::File::create(path)?;
+        let mut encoder = png::Encoder::new(io::BufWriter::new(file), self.width, self.height);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// Reads a RGBA PNG file, like the ones written by `save_png`.
+    pub fn load_png(path: impl AsRef<Path>) -> io::Result<Bitmap> {
+        let file = std::fs::File::open(path)?;
+        let (info, mut reader) = png::Decoder::new(io::BufReader::new(file))
+            .read_info()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+            let msg = format!("Expected 8 bit RGBA but got {:?}", info.color_type);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+        let mut pixels = vec![0; info.buffer_size()];
+        reader
+            .next_frame(&mut pixels)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Bitmap::from_rgba(info.width, info.height, pixels))
+    }
+
+    pub(crate) fn resize(&mut self, size: Size) {
+        self.width = size.width.max(0.0).round() as u32;
+        self.height = size.height.max(0.0).round() as u32;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        let len = self.width as usize * self.height as usize;
+        self.pixels.clear();
+        self.pixels.reserve(len * 4);
+        for _ in 0..len {
+            self.pixels.extend_from_slice(&to_rgba(CLEAR_COLOR));
+        }
+        self.clips.clear();
+    }
+
+    /// The region that may currently be drawn to.
+    fn clip(&self) -> PixelRect {
+        let frame = PixelRect {
+            min_x: 0,
+            min_y: 0,
+            max_x: self.width as i32,
+            max_y: self.height as i32,
+        };
+        self.clips.last().cloned().unwrap_or(frame)
+    }
+
+    /// Blends `color` with the given `coverage` over the pixel at `x` and `y`.
+    fn blend(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let pixel = &mut self.pixels[index..index + 4];
+
+        let alpha = color.a * coverage;
+        let dst_alpha = f32::from(pixel[3]) / 255.0;
+        let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+        if out_alpha <= 0.0 {
+            return;
+        }
+
+        let channels = [color.r, color.g, color.b];
+        for (dst, src) in pixel.iter_mut().zip(channels.iter()) {
+            let dst_value = f32::from(*dst) / 255.0;
+            let value = (src * alpha + dst_value * dst_alpha * (1.0 - alpha)) / out_alpha;
+            *dst = to_byte(value);
+        }
+        pixel[3] = to_byte(out_alpha);
+    }
+}
+
+impl HeadlessRenderer for SoftwareRenderer {
+    fn begin_frame(&mut self, size: Size) {
+        self.resize(size);
+        self.clear();
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn fonts(&self) -> &FontManager {
+        &self.fonts
+    }
+
+    fn add_font(&mut self, font: Font, data: Vec<u8>) {
+        self.fonts.add_font(font, data);
+    }
+
+    fn remove_font(&mut self, font: &Font) {
+        self.fonts.remove_font(font);
+    }
+
+    fn rect(&mut self, bounds: Bounds, color: Color) {
+        let area = PixelRect::from_bounds(bounds).intersection(self.clip());
+        for y in area.min_y..area.max_y {
+            for x in area.min_x..area.max_x {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    fn glyphs(
+        &mut self,
+        bounds: Bounds,
+        font: &Font,
+        size: FontSize,
+        color: Color,
+        glyphs: &[LayoutGlyph],
+    ) {
+        if !self.fonts.contains(font) {
+            log::error!("Tried to render glyphs of unknown {:?}", font);
+            return;
+        }
+
+        let area = PixelRect::from_bounds(bounds).intersection(self.clip());
+        let scale = FontManager::scale(size);
+        let mut coverages = Vec::new();
+
+        {
+            let rt_font = self.fonts.rusttype(font);
+            for glyph in glyphs {
+                let origin = bounds.origin + glyph.bounds.origin.to_vector();
+                let point = rusttype::point(origin.x, origin.y);
+                let positioned = rt_font
+                    .glyph(rusttype::GlyphId(glyph.index))
+                    .scaled(scale)
+                    .positioned(point);
+                if let Some(bb) = positioned.pixel_bounding_box() {
+                    positioned.draw(|x, y, coverage| {
+                        let (x, y) = (bb.min.x + x as i32, bb.min.y + y as i32);
+                        if area.contains(x, y) {
+                            coverages.push((x, y, coverage));
+                        }
+                    });
+                }
+            }
+        }
+
+        for (x, y, coverage) in coverages {
+            self.blend(x, y, color, coverage);
+        }
+    }
+
+    fn image(&mut self, bounds: Bounds, bitmap: &Bitmap) {
+        // There is nothing to sample from
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return;
+        }
+
+        let target = PixelRect::from_bounds(bounds);
+        let area = target.intersection(self.clip());
+        let width = (target.max_x - target.min_x) as f32;
+        let height = (target.max_y - target.min_y) as f32;
+
+        for y in area.min_y..area.max_y {
+            for x in area.min_x..area.max_x {
+                // Nearest neighbour sampling
+                let u = (x - target.min_x) as f32 / width;
+                let v = (y - target.min_y) as f32 / height;
+                let src_x = ((u * bitmap.width() as f32) as u32).min(bitmap.width() - 1);
+                let src_y = ((v * bitmap.height() as f32) as u32).min(bitmap.height() - 1);
+                let index = (src_y * bitmap.width() + src_x) as usize * 4;
+                let pixel = &bitmap.pixels()[index..index + 4];
+                let color = Color::rgba(
+                    f32::from(pixel[0]) / 255.0,
+                    f32::from(pixel[1]) / 255.0,
+                    f32::from(pixel[2]) / 255.0,
+                    f32::from(pixel[3]) / 255.0,
+                );
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    fn push_clip(&mut self, bounds: Bounds) {
+        let clip = PixelRect::from_bounds(bounds).intersection(self.clip());
+        self.clips.push(clip);
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips.pop().is_none() {
+            log::error!("Tried to pop a clip but there is none");
+        }
+    }
+}
+
+/// Compares the current frame of `renderer` with the PNG file at `path`.
+///
+/// On mismatch the current frame is written next to it with an additional
+/// `.new` before the extension and the calling test fails.
+/// Review the difference and replace the old file to accept it.
+pub fn assert_golden_image(path: impl AsRef<Path>, renderer: &SoftwareRenderer) {
+    let path = path.as_ref();
+    let expected = SoftwareRenderer::load_png(path).ok();
+    let matches = |expected: &Bitmap| {
+        expected.width() == renderer.width()
+            && expected.height() == renderer.height()
+            && expected.pixels() == renderer.pixels()
+    };
+    if expected.as_ref().map_or(false, matches) {
+        return;
+    }
+
+    let mut new_path = PathBuf::from(path);
+    let mut extension = String::from("new");
+    if let Some(ext) = new_path.extension() {
+        extension = extension + "." + &ext.to_string_lossy();
+    }
+    new_path.set_extension(extension);
+    renderer.save_png(&new_path).unwrap();
+
+    match expected {
+        None => panic!(
+            "There is no golden image at {}, the current frame has been written to {}",
+            path.display(),
+            new_path.display()
+        ),
+        Some(expected) => {
+            let differing = expected
+                .pixels()
+                .chunks(4)
+                .zip(renderer.pixels().chunks(4))
+                .filter(|(expected, actual)| expected != actual)
+                .count();
+            panic!(
+                "The frame differs from the golden image {} ({}x{} with {} differing pixels \
+                 instead of {}x{}), the new one has been written to {}",
+                path.display(),
+                renderer.width(),
+                renderer.height(),
+                differing,
+                expected.width(),
+                expected.height(),
+                new_path.display()
+            )
+        }
+    }
+}
+
+fn to_byte(value: f32) -> u8 {
+    (value.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+fn to_rgba(color: Color) -> [u8; 4] {
+    [
+        to_byte(color.r),
+        to_byte(color.g),
+        to_byte(color.b),
+        to_byte(color.a),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn test_clipped_rect() {
+        let mut renderer = SoftwareRenderer::default();
+        renderer.begin_frame(Size::new(4.0, 4.0));
+
+        renderer.push_clip(Bounds::new(Position::new(1.0, 1.0), Size::new(2.0, 2.0)));
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        renderer.rect(Bounds::new(Position::zero(), Size::new(4.0, 4.0)), red);
+        renderer.pop_clip();
+
+        assert_eq!(renderer.pixel(0, 0), to_rgba(CLEAR_COLOR));
+        assert_eq!(renderer.pixel(1, 1), [255, 0, 0, 255]);
+        assert_eq!(renderer.pixel(2, 2), [255, 0, 0, 255]);
+        assert_eq!(renderer.pixel(3, 3), to_rgba(CLEAR_COLOR));
+    }
+
+    #[test]
+    fn test_empty_image() {
+        let mut renderer = SoftwareRenderer::default();
+        renderer.begin_frame(Size::new(4.0, 4.0));
+
+        let bitmap = Bitmap::from_rgba(0, 0, Vec::new());
+        renderer.image(Bounds::new(Position::zero(), Size::new(4.0, 4.0)), &bitmap);
+
+        assert_eq!(renderer.pixel(0, 0), to_rgba(CLEAR_COLOR));
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let mut renderer = SoftwareRenderer::default();
+        renderer.begin_frame(Size::new(3.0, 2.0));
+        let red = Color::rgba(1.0, 0.0, 0.0, 0.5);
+        renderer.rect(Bounds::new(Position::zero(), Size::new(1.0, 2.0)), red);
+
+        let path = std::env::temp_dir().join("coat_test_png_round_trip.png");
+        renderer.save_png(&path).unwrap();
+        let bitmap = SoftwareRenderer::load_png(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!((bitmap.width(), bitmap.height()), (3, 2));
+        assert_eq!(bitmap.pixels(), renderer.pixels());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Font, Size};
+use crate::{Bitmap, Bounds, Color, Font, FontManager, FontSize, LayoutGlyph, Renderer, Size};
 use gleam::gl;
 use std::rc::Rc;
 use webrender::api::*;
@@ -6,8 +6,11 @@ use webrender::api::*;
 mod notifier;
 use self::notifier::Notifier;
 
-mod font_manager;
-pub use self::font_manager::FontManager;
+mod font_keys;
+use self::font_keys::FontKeys;
+
+mod image_keys;
+use self::image_keys::ImageKeys;
 
 pub struct Webrenderer {
     renderer: webrender::Renderer,
@@ -15,9 +18,11 @@ pub struct Webrenderer {
     device_size: DeviceIntSize,
     document_id: DocumentId,
     pipeline_id: PipelineId,
-    pub api: RenderApi,
-    pub font_manager: FontManager,
-    pub builder: DisplayListBuilder,
+    api: RenderApi,
+    font_manager: FontManager,
+    font_keys: FontKeys,
+    image_keys: ImageKeys,
+    builder: DisplayListBuilder,
 }
 
 impl Webrenderer {
@@ -37,6 +42,8 @@ impl Webrenderer {
         let device_size = DeviceIntSize::new(0, 0);
         let document_id = api.add_document(device_size, 0);
         let font_manager = FontManager::default();
+        let font_keys = FontKeys::default();
+        let image_keys = ImageKeys::default();
         let builder = DisplayListBuilder::new(pipeline_id, layout_size);
 
         {
@@ -53,18 +60,12 @@ impl Webrenderer {
             document_id,
             pipeline_id,
             font_manager,
+            font_keys,
+            image_keys,
             builder,
         }
     }
 
-    pub(crate) fn add_font(&mut self, font: Font, data: impl Into<Vec<u8>>) {
-        self.font_manager.add_font(font, data.into(), &self.api);
-    }
-
-    pub(crate) fn remove_font(&mut self, font: &Font) {
-        self.font_manager.remove_font(font, &self.api)
-    }
-
     pub(crate) fn resize(&mut self, size: Size, dpr: f32) {
         let Size { width, height, .. } = size;
         self.layout_size = LayoutSize::new(width, height);
@@ -87,6 +88,7 @@ impl Webrenderer {
         txn.set_display_list(Epoch(0), None, self.layout_size, builder.finalize(), true);
         txn.generate_frame();
         self.api.send_transaction(self.document_id, txn);
+        self.image_keys.collect_unused(&self.api);
     }
 
     pub(crate) fn flush(&mut self) {
@@ -99,3 +101,101 @@ impl Webrenderer {
         self.renderer.deinit();
     }
 }
+
+impl Renderer for Webrenderer {
+    fn fonts(&self) -> &FontManager {
+        &self.font_manager
+    }
+
+    fn add_font(&mut self, font: Font, data: Vec<u8>) {
+        self.font_keys
+            .add_font(font.clone(), data.clone(), &self.api);
+        self.font_manager.add_font(font, data);
+    }
+
+    fn remove_font(&mut self, font: &Font) {
+        self.font_keys.remove_font(font, &self.api);
+        self.font_manager.remove_font(font);
+    }
+
+    fn rect(&mut self, bounds: Bounds, color: Color) {
+        let info = LayoutPrimitiveInfo::new(LayoutRect::from_untyped(&bounds));
+        self.builder.push_rect(&info, color_f(color));
+    }
+
+    fn glyphs(
+        &mut self,
+        bounds: Bounds,
+        font: &Font,
+        size: FontSize,
+        color: Color,
+        glyphs: &[LayoutGlyph],
+    ) {
+        let font_key = match self.font_keys.instance(font, size, &self.api) {
+            Some(font_key) => font_key,
+            None => return,
+        };
+
+        let wr_glyph = |g: &LayoutGlyph| GlyphInstance {
+            index: g.index,
+            point: LayoutPoint::from_untyped(&(bounds.origin + g.bounds.origin.to_vector())),
+        };
+        let glyphs = glyphs.iter().map(wr_glyph);
+
+        let info = LayoutPrimitiveInfo::new(LayoutRect::from_untyped(&bounds));
+
+        let mut text_flags = FontInstanceFlags::empty();
+        text_flags.set(FontInstanceFlags::SUBPIXEL_BGR, true);
+        text_flags.set(FontInstanceFlags::LCD_VERTICAL, true);
+        let text_options = GlyphOptions {
+            render_mode: FontRenderMode::Subpixel,
+            flags: text_flags,
+        };
+
+        let item = SpecificDisplayItem::Text(TextDisplayItem {
+            color: color_f(color),
+            font_key,
+            glyph_options: Some(text_options),
+        });
+
+        // TODO: Will no longer work with newer webrender
+        self.builder.push_item(&item, &info);
+        // TODO: This is DANGEROUS! It should check for webrenders MAX_TEXT_RUN_LENGTH
+        self.builder.push_iter(glyphs);
+    }
+
+    fn image(&mut self, bounds: Bounds, bitmap: &Bitmap) {
+        // Webrender does not accept empty images
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return;
+        }
+
+        let key = self.image_keys.key(bitmap, &self.api);
+        let info = LayoutPrimitiveInfo::new(LayoutRect::from_untyped(&bounds));
+        self.builder.push_image(
+            &info,
+            LayoutSize::from_untyped(&bounds.size),
+            LayoutSize::zero(),
+            ImageRendering::Auto,
+            AlphaType::PremultipliedAlpha,
+            key,
+            ColorF::WHITE,
+        );
+    }
+
+    fn push_clip(&mut self, bounds: Bounds) {
+        let rect = LayoutRect::from_untyped(&bounds);
+        let id = self
+            .builder
+            .define_clip(rect, Vec::<ComplexClipRegion>::new(), None);
+        self.builder.push_clip_id(id);
+    }
+
+    fn pop_clip(&mut self) {
+        self.builder.pop_clip_id();
+    }
+}
+
+fn color_f(color: Color) -> ColorF {
+    ColorF::new(color.r, color.g, color.b, color.a)
+}
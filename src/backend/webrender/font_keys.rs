@@ -0,0 +1,72 @@
+use crate::{Font, FontSize};
+use fnv::FnvHashMap as HashMap;
+use webrender::api::{
+    AddFont, AddFontInstance, FontInstanceKey, FontKey, RenderApi, ResourceUpdate,
+};
+
+struct LoadedFont {
+    key: FontKey,
+    instances: HashMap<FontSize, FontInstanceKey>,
+}
+
+/// Maps the fonts of the `FontManager` to their webrender resources.
+#[derive(Default)]
+pub(crate) struct FontKeys {
+    fonts: HashMap<Font, LoadedFont>,
+}
+
+impl FontKeys {
+    pub(crate) fn add_font(&mut self, font: Font, data: Vec<u8>, api: &RenderApi) {
+        let key = api.generate_font_key();
+        api.update_resources(vec![ResourceUpdate::AddFont(AddFont::Raw(key, data, 0))]);
+        self.fonts.insert(
+            font,
+            LoadedFont {
+                key,
+                instances: HashMap::default(),
+            },
+        );
+    }
+
+    pub(crate) fn remove_font(&mut self, font: &Font, api: &RenderApi) {
+        if let Some(font) = self.fonts.remove(font) {
+            let mut updates = Vec::default();
+            for instance in font.instances.values() {
+                updates.push(ResourceUpdate::DeleteFontInstance(*instance));
+            }
+            updates.push(ResourceUpdate::DeleteFont(font.key));
+            api.update_resources(updates);
+        }
+    }
+
+    pub(crate) fn instance(
+        &mut self,
+        font: &Font,
+        size: FontSize,
+        api: &RenderApi,
+    ) -> Option<FontInstanceKey> {
+        if let Some(loaded_font) = self.fonts.get_mut(font) {
+            let key = loaded_font
+                .instances
+                .get(&size)
+                .cloned()
+                .unwrap_or_else(|| {
+                    let key = api.generate_font_instance_key();
+                    api.update_resources(vec![ResourceUpdate::AddFontInstance(AddFontInstance {
+                        key,
+                        font_key: loaded_font.key,
+                        glyph_size: app_units::Au::from_px(size),
+                        options: None,
+                        platform_options: None,
+                        variations: Vec::new(),
+                    })]);
+                    loaded_font.instances.insert(size, key);
+                    key
+                });
+            Some(key)
+        } else {
+            log::error!("Tried to receive instance of unknown {:?}", font);
+            None
+        }
+    }
+}
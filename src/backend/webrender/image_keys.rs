@@ -0,0 +1,65 @@
+use crate::Bitmap;
+use fnv::{FnvHashMap as HashMap, FnvHashSet as HashSet};
+use webrender::api::{
+    AddImage, ImageData, ImageDescriptor, ImageFormat, ImageKey, RenderApi, ResourceUpdate,
+};
+
+/// Uploads `Bitmap`s on demand and deletes them once they are no longer drawn.
+#[derive(Default)]
+pub(crate) struct ImageKeys {
+    keys: HashMap<usize, ImageKey>,
+    used: HashSet<usize>,
+}
+
+impl ImageKeys {
+    pub(crate) fn key(&mut self, bitmap: &Bitmap, api: &RenderApi) -> ImageKey {
+        self.used.insert(bitmap.id());
+        *self.keys.entry(bitmap.id()).or_insert_with(|| {
+            let key = api.generate_image_key();
+            let descriptor = ImageDescriptor::new(
+                bitmap.width() as i32,
+                bitmap.height() as i32,
+                ImageFormat::BGRA8,
+                false,
+                false,
+            );
+            api.update_resources(vec![ResourceUpdate::AddImage(AddImage {
+                key,
+                descriptor,
+                data: ImageData::new(to_premultiplied_bgra(bitmap.pixels())),
+                tiling: None,
+            })]);
+            key
+        })
+    }
+
+    /// Deletes all images that have not been drawn since the last call.
+    pub(crate) fn collect_unused(&mut self, api: &RenderApi) {
+        let used = &self.used;
+        let mut updates = Vec::new();
+        self.keys.retain(|id, key| {
+            let keep = used.contains(id);
+            if !keep {
+                updates.push(ResourceUpdate::DeleteImage(*key));
+            }
+            keep
+        });
+        if !updates.is_empty() {
+            api.update_resources(updates);
+        }
+        self.used.clear();
+    }
+}
+
+fn to_premultiplied_bgra(rgba: &[u8]) -> Vec<u8> {
+    let mut bgra = Vec::with_capacity(rgba.len());
+    for pixel in rgba.chunks(4) {
+        let alpha = u16::from(pixel[3]);
+        let premultiply = |channel: u8| (u16::from(channel) * alpha / 255) as u8;
+        bgra.push(premultiply(pixel[2]));
+        bgra.push(premultiply(pixel[1]));
+        bgra.push(premultiply(pixel[0]));
+        bgra.push(pixel[3]);
+    }
+    bgra
+}
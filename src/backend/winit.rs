@@ -1,11 +1,25 @@
+mod alarm;
+use self::alarm::Alarm;
+
 mod eventloop;
 pub use self::eventloop::EventLoop;
 
 mod window;
-pub use self::window::{AppEvent, Window};
+pub use self::window::{NewWindow, Window};
+
+mod window_renderer;
+pub use self::window_renderer::RenderBackend;
+use self::window_renderer::WindowRenderer;
+
+mod application;
+pub use self::application::Application;
+
+mod app_event;
+pub use self::app_event::{AppEvent, CursorIcon};
+
+mod ui_handle;
+use self::ui_handle::Inbox;
+pub use self::ui_handle::UiHandle;
 
 mod event_handler;
 use self::event_handler::EventHandler;
-
-pub(crate) static DEFAULT_FONT: &[u8] = include_bytes!("../../assets/fonts/OpenSans-Regular.ttf");
-pub(crate) static DEFAULT_FONT_NAME: &str = "OpenSans";
@@ -0,0 +1,61 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+/// Wakes the `EventsLoop` at a deadline, as winit can only wait indefinitely.
+pub struct Alarm {
+    shared: Arc<(Mutex<AlarmState>, Condvar)>,
+}
+
+#[derive(Default)]
+struct AlarmState {
+    deadline: Option<Instant>,
+    stopped: bool,
+}
+
+impl Alarm {
+    pub fn new(proxy: winit::EventsLoopProxy) -> Self {
+        let shared = Arc::new((Mutex::new(AlarmState::default()), Condvar::new()));
+        let thread_shared = shared.clone();
+
+        std::thread::Builder::new()
+            .name("coat-alarm".into())
+            .spawn(move || run(&thread_shared, &proxy))
+            .expect("Failed to spawn the alarm thread");
+
+        Alarm { shared }
+    }
+
+    /// Replaces the current deadline, `None` disables the alarm.
+    pub fn set(&self, deadline: Option<Instant>) {
+        let (state, condvar) = &*self.shared;
+        state.lock().unwrap().deadline = deadline;
+        condvar.notify_one();
+    }
+}
+
+impl Drop for Alarm {
+    fn drop(&mut self) {
+        let (state, condvar) = &*self.shared;
+        state.lock().unwrap().stopped = true;
+        condvar.notify_one();
+    }
+}
+
+fn run(shared: &(Mutex<AlarmState>, Condvar), proxy: &winit::EventsLoopProxy) {
+    let (state, condvar) = shared;
+    let mut state = state.lock().unwrap();
+    while !state.stopped {
+        state = match state.deadline {
+            None => condvar.wait(state).unwrap(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if deadline <= now {
+                    state.deadline = None;
+                    let _ = proxy.wakeup();
+                    continue;
+                }
+                condvar.wait_timeout(state, deadline - now).unwrap().0
+            }
+        };
+    }
+}
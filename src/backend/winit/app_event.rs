@@ -0,0 +1,114 @@
+use crate::backend::winit::NewWindow;
+use crate::Size;
+
+/// The events a root component can emit to control its window.
+///
+/// Minimizing is not supported by the current winit version,
+/// so `SetMinimized` only logs a warning for now.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    SetTitle(String),
+    /// Closes all windows and stops the application.
+    Quit,
+
+    /// Opens another window next to this one.
+    OpenWindow(NewWindow),
+    /// Closes only this window.
+    CloseWindow,
+
+    /// Resizes the inner area of the window.
+    Resize(Size),
+    SetMinSize(Option<Size>),
+    SetMaxSize(Option<Size>),
+
+    SetFullscreen(bool),
+    ToggleFullscreen,
+    SetMaximized(bool),
+    /// Not supported yet, winit 0.18 has no way to minimize a window.
+    SetMinimized(bool),
+
+    SetCursor(CursorIcon),
+}
+
+/// The icon of the mouse cursor while it is above the window.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum CursorIcon {
+    Default,
+    Crosshair,
+    Hand,
+    Arrow,
+    Move,
+    Text,
+    Wait,
+    Help,
+    Progress,
+    NotAllowed,
+    ContextMenu,
+    Cell,
+    VerticalText,
+    Alias,
+    Copy,
+    NoDrop,
+    Grab,
+    Grabbing,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+}
+
+impl From<CursorIcon> for winit::MouseCursor {
+    fn from(other: CursorIcon) -> winit::MouseCursor {
+        use winit::MouseCursor;
+        match other {
+            CursorIcon::Default => MouseCursor::Default,
+            CursorIcon::Crosshair => MouseCursor::Crosshair,
+            CursorIcon::Hand => MouseCursor::Hand,
+            CursorIcon::Arrow => MouseCursor::Arrow,
+            CursorIcon::Move => MouseCursor::Move,
+            CursorIcon::Text => MouseCursor::Text,
+            CursorIcon::Wait => MouseCursor::Wait,
+            CursorIcon::Help => MouseCursor::Help,
+            CursorIcon::Progress => MouseCursor::Progress,
+            CursorIcon::NotAllowed => MouseCursor::NotAllowed,
+            CursorIcon::ContextMenu => MouseCursor::ContextMenu,
+            CursorIcon::Cell => MouseCursor::Cell,
+            CursorIcon::VerticalText => MouseCursor::VerticalText,
+            CursorIcon::Alias => MouseCursor::Alias,
+            CursorIcon::Copy => MouseCursor::Copy,
+            CursorIcon::NoDrop => MouseCursor::NoDrop,
+            CursorIcon::Grab => MouseCursor::Grab,
+            CursorIcon::Grabbing => MouseCursor::Grabbing,
+            CursorIcon::AllScroll => MouseCursor::AllScroll,
+            CursorIcon::ZoomIn => MouseCursor::ZoomIn,
+            CursorIcon::ZoomOut => MouseCursor::ZoomOut,
+            CursorIcon::EResize => MouseCursor::EResize,
+            CursorIcon::NResize => MouseCursor::NResize,
+            CursorIcon::NeResize => MouseCursor::NeResize,
+            CursorIcon::NwResize => MouseCursor::NwResize,
+            CursorIcon::SResize => MouseCursor::SResize,
+            CursorIcon::SeResize => MouseCursor::SeResize,
+            CursorIcon::SwResize => MouseCursor::SwResize,
+            CursorIcon::WResize => MouseCursor::WResize,
+            CursorIcon::EwResize => MouseCursor::EwResize,
+            CursorIcon::NsResize => MouseCursor::NsResize,
+            CursorIcon::NeswResize => MouseCursor::NeswResize,
+            CursorIcon::NwseResize => MouseCursor::NwseResize,
+            CursorIcon::ColResize => MouseCursor::ColResize,
+            CursorIcon::RowResize => MouseCursor::RowResize,
+        }
+    }
+}
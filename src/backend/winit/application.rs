@@ -0,0 +1,304 @@
+use crate::backend::winit::window::{to_logical_size, RootPointer};
+use crate::backend::winit::{AppEvent, EventHandler, EventLoop, Inbox, NewWindow, WindowRenderer};
+use crate::backend::{DEFAULT_FONT, DEFAULT_FONT_NAME};
+use crate::{Cid, Font, Input, Renderer, Size, UiData, UiLayout, UiRender, UiUpdate};
+use gleam::gl;
+use glutin::GlContext;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Drives any number of windows with one event loop.
+///
+/// The application quits once its last window has been closed
+/// or one of the root components emitted `AppEvent::Quit`.
+pub struct Application {
+    fonts: Vec<(Font, Vec<u8>)>,
+    windows: Vec<NewWindow>,
+}
+
+impl Default for Application {
+    fn default() -> Self {
+        Application::new()
+    }
+}
+
+impl Application {
+    pub fn new() -> Self {
+        Application {
+            fonts: vec![(Font::from_family(DEFAULT_FONT_NAME), DEFAULT_FONT.to_vec())],
+            windows: Vec::new(),
+        }
+    }
+
+    /// Adds a font to every window of this application.
+    pub fn font(mut self, font: Font, data: impl Into<Vec<u8>>) -> Self {
+        self.fonts.push((font, data.into()));
+        self
+    }
+
+    /// Opens `window` once the application runs.
+    pub fn window(mut self, window: NewWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    pub fn run(self) {
+        let mut eventloop = EventLoop::new();
+        let fonts = self.fonts;
+
+        let mut windows: Vec<AppWindow> = self
+            .windows
+            .into_iter()
+            .map(|window| AppWindow::open(window, &eventloop, &fonts))
+            .collect();
+
+        'main: while !windows.is_empty() {
+            let interval = windows.iter().map(|w| w.frame_interval).min().unwrap();
+            let frame = windows.iter().any(|w| w.data.frames.is_requested());
+            let deadline = windows.iter().filter_map(AppWindow::next_deadline).min();
+            for event in eventloop.next(interval, frame, deadline) {
+                let window_id = match event {
+                    winit::Event::WindowEvent { window_id, .. } => window_id,
+                    _ => continue,
+                };
+                if let Some(index) = windows.iter().position(|w| w.id() == window_id) {
+                    if !windows[index].handle_event(event) {
+                        windows.remove(index).close();
+                    }
+                }
+            }
+
+            // Windows opened during this loop get updated right away.
+            let mut index = 0;
+            while index < windows.len() {
+                let mut close = false;
+                for request in windows[index].update() {
+                    match request {
+                        AppRequest::Quit => break 'main,
+                        AppRequest::CloseWindow => close = true,
+                        AppRequest::OpenWindow(window) => {
+                            windows.push(AppWindow::open(window, &eventloop, &fonts));
+                        }
+                    }
+                }
+
+                if close {
+                    windows.remove(index).close();
+                } else {
+                    windows[index].flush();
+                    index += 1;
+                }
+            }
+        }
+
+        for window in windows {
+            window.close();
+        }
+    }
+}
+
+/// The `AppEvent`s that concern the whole `Application` instead of a single window.
+enum AppRequest {
+    Quit,
+    CloseWindow,
+    OpenWindow(NewWindow),
+}
+
+/// One open window with its own `UiData`, renderer and input.
+struct AppWindow {
+    window: glutin::GlWindow,
+    renderer: WindowRenderer,
+    root: RootPointer,
+    inbox: Inbox,
+    data: UiData,
+    input: Input,
+    ehandler: EventHandler,
+    app_id: Cid,
+    size: Size,
+    frame_interval: Duration,
+    dpr: f32,
+    resized: bool,
+    fullscreen: bool,
+}
+
+impl AppWindow {
+    fn open(new: NewWindow, eventloop: &EventLoop, fonts: &[(Font, Vec<u8>)]) -> Self {
+        let NewWindow {
+            mut window,
+            root,
+            inbox,
+        } = new;
+        let size = window.size;
+        let fullscreen = window.fullscreen;
+        let frame_interval = window.frame_interval;
+        let backend = window.renderer;
+        inbox.connect(eventloop.create_proxy());
+        let window = window.build(eventloop);
+
+        unsafe {
+            window.make_current().ok();
+        }
+
+        let gl = match window.get_api() {
+            glutin::Api::OpenGl => unsafe {
+                gl::GlFns::load_with(|symbol| window.get_proc_address(symbol) as *const _)
+            },
+            glutin::Api::OpenGlEs => unsafe {
+                gl::GlesFns::load_with(|symbol| window.get_proc_address(symbol) as *const _)
+            },
+            glutin::Api::WebGl => unimplemented!(),
+        };
+
+        let dpr = window.get_hidpi_factor() as f32;
+
+        let mut renderer = WindowRenderer::new(backend, eventloop.create_proxy(), gl, dpr);
+        renderer.resize(size, dpr);
+        for (font, data) in fonts {
+            renderer.add_font(font.clone(), data.clone());
+        }
+
+        let mut data = UiData::default();
+        let app_id = data.fresh_id();
+        let proxy = Mutex::new(eventloop.create_proxy());
+        data.tasks.set_wake_hook(Arc::new(move || {
+            let _ = proxy.lock().unwrap().wakeup();
+        }));
+
+        AppWindow {
+            window,
+            renderer,
+            root,
+            inbox,
+            data,
+            input: Input::new(),
+            ehandler: EventHandler::new(),
+            app_id,
+            size,
+            frame_interval,
+            dpr,
+            resized: false,
+            fullscreen,
+        }
+    }
+
+    /// When the next timer of this window fires.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.data.timers.next_deadline()
+    }
+
+    fn id(&self) -> winit::WindowId {
+        self.window.id()
+    }
+
+    /// Returns `false` if the window should be closed.
+    fn handle_event(&mut self, event: winit::Event) -> bool {
+        use winit::{Event, WindowEvent};
+        if let Event::WindowEvent { ref event, .. } = event {
+            match event {
+                WindowEvent::CloseRequested => {
+                    return false;
+                }
+                WindowEvent::Resized(lsize) => {
+                    self.size = Size::new(lsize.width as f32, lsize.height as f32);
+                    self.renderer.resize(self.size, self.dpr);
+                    self.resized = true;
+                }
+                WindowEvent::HiDpiFactorChanged(new_dpr) => {
+                    self.dpr = (*new_dpr) as f32;
+                    self.renderer.resize(self.size, self.dpr);
+                }
+                _ => (),
+            }
+        }
+        if let Some(event) = self.ehandler.convert_winit_event(event) {
+            self.input.push_event(event);
+        }
+        true
+    }
+
+    /// Runs the pipeline and returns the `AppEvent`s
+    /// that concern the whole application.
+    fn update(&mut self) -> Vec<AppRequest> {
+        let mut requests = Vec::new();
+        let data = &mut self.data;
+        let renderer = &mut self.renderer;
+        let app_id = self.app_id;
+
+        let delivered = data.begin_frame(Instant::now())
+            | self.inbox.deliver(data, app_id)
+            | data.poll_tasks()
+            | data.poll_timers();
+
+        // This is where everything happens!
+        if (self.root.input)(data, &mut self.input, app_id) || delivered || self.resized {
+            let updated = UiUpdate::run(data, renderer, app_id);
+
+            for event in data.take_events::<AppEvent>(app_id) {
+                match event {
+                    AppEvent::Quit => requests.push(AppRequest::Quit),
+                    AppEvent::CloseWindow => requests.push(AppRequest::CloseWindow),
+                    AppEvent::OpenWindow(window) => requests.push(AppRequest::OpenWindow(window)),
+                    event => handle_app_event(&self.window, event, &mut self.fullscreen),
+                }
+            }
+
+            if updated || self.resized {
+                (self.root.view)(data, renderer, app_id);
+                UiLayout::run(data, app_id, self.size);
+                renderer.begin_frame();
+                UiRender::run(data, renderer, app_id);
+                renderer.render();
+            }
+        }
+
+        self.resized = false;
+        self.input.clear_events();
+        self.inbox.wake_if_pending();
+        requests
+    }
+
+    fn flush(&mut self) {
+        unsafe {
+            self.window.make_current().ok();
+        }
+        self.renderer.flush();
+        self.window.swap_buffers().ok();
+    }
+
+    fn close(self) {
+        unsafe {
+            self.window.make_current().ok();
+        }
+        self.renderer.deinit();
+    }
+}
+
+/// Applies the `AppEvent`s that only concern this `window`.
+fn handle_app_event(window: &glutin::GlWindow, event: AppEvent, fullscreen: &mut bool) {
+    match event {
+        AppEvent::SetTitle(title) => window.set_title(&title),
+        // These are turned into `AppRequest`s by `AppWindow::update`
+        AppEvent::Quit | AppEvent::CloseWindow | AppEvent::OpenWindow(_) => (),
+        AppEvent::Resize(size) => window.set_inner_size(to_logical_size(size)),
+        AppEvent::SetMinSize(size) => window.set_min_dimensions(size.map(to_logical_size)),
+        AppEvent::SetMaxSize(size) => window.set_max_dimensions(size.map(to_logical_size)),
+        AppEvent::SetFullscreen(value) => {
+            *fullscreen = value;
+            let monitor = if value {
+                Some(window.get_current_monitor())
+            } else {
+                None
+            };
+            window.set_fullscreen(monitor);
+        }
+        AppEvent::ToggleFullscreen => {
+            let value = !*fullscreen;
+            handle_app_event(window, AppEvent::SetFullscreen(value), fullscreen);
+        }
+        AppEvent::SetMaximized(maximized) => window.set_maximized(maximized),
+        AppEvent::SetMinimized(_) => {
+            log::warn!("`AppEvent::SetMinimized` is not supported by winit 0.18 and is ignored")
+        }
+        AppEvent::SetCursor(icon) => window.set_cursor(icon.into()),
+    }
+}
@@ -1,17 +1,23 @@
+use crate::backend::winit::Alarm;
+use std::time::{Duration, Instant};
 use winit::{ControlFlow, Event, EventsLoop, EventsLoopProxy};
 
 pub struct EventLoop {
     ui_needs_update: bool,
-    last_update: std::time::Instant,
+    last_update: Instant,
     events_loop: EventsLoop,
+    alarm: Alarm,
 }
 
 impl EventLoop {
     pub fn new() -> Self {
+        let events_loop = EventsLoop::new();
+        let alarm = Alarm::new(events_loop.create_proxy());
         EventLoop {
-            last_update: std::time::Instant::now(),
+            last_update: Instant::now(),
             ui_needs_update: true,
-            events_loop: EventsLoop::new(),
+            events_loop,
+            alarm,
         }
     }
 
@@ -24,14 +30,20 @@ impl EventLoop {
     }
 
     /// Produce an iterator yielding all available events.
-    pub fn next(&mut self) -> Vec<Event> {
-        // We don't want to loop any faster than 60 FPS, so wait until it has been at least 16ms
-        // since the last yield.
-        let last_update = self.last_update;
-        let sixteen_ms = std::time::Duration::from_millis(16);
-        let duration_since_last_update = std::time::Instant::now().duration_since(last_update);
-        if duration_since_last_update < sixteen_ms {
-            std::thread::sleep(sixteen_ms - duration_since_last_update);
+    ///
+    /// Loops at most once per `frame_interval`. Unless a new `frame` has been requested
+    /// this waits for the next event, but not past the `deadline`.
+    pub fn next(
+        &mut self,
+        frame_interval: Duration,
+        frame: bool,
+        deadline: Option<Instant>,
+    ) -> Vec<Event> {
+        // We don't want to loop any faster than the frame rate, so wait until
+        // it has been at least one `frame_interval` since the last yield.
+        let duration_since_last_update = Instant::now().duration_since(self.last_update);
+        if duration_since_last_update < frame_interval {
+            std::thread::sleep(frame_interval - duration_since_last_update);
         }
 
         // Collect all pending events.
@@ -39,11 +51,14 @@ impl EventLoop {
         self.events_loop.poll_events(|event| events.push(event));
 
         // If there are no events and the `Ui` does not need updating, wait for the next event.
-        if events.is_empty() && !self.ui_needs_update {
+        let expired = deadline.map_or(false, |deadline| deadline <= Instant::now());
+        if events.is_empty() && !self.ui_needs_update && !frame && !expired {
+            self.alarm.set(deadline);
             self.events_loop.run_forever(|event| {
                 events.push(event);
                 ControlFlow::Break
             });
+            self.alarm.set(None);
         }
 
         self.ui_needs_update = false;
@@ -0,0 +1,152 @@
+use crate::{Cid, Component, UiData};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+type Delivery = Box<dyn FnOnce(&mut UiData, Cid) + Send>;
+
+/// Sends messages into a running `Window` from any thread.
+///
+/// Created by `Window::root`, the messages are delivered right
+/// before the next `UiUpdate` of the window.
+pub struct UiHandle<Comp: Component> {
+    inbox: Inbox,
+    marker: PhantomData<fn(Comp)>,
+}
+
+impl<Comp: Component> Clone for UiHandle<Comp> {
+    fn clone(&self) -> Self {
+        UiHandle {
+            inbox: self.inbox.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Comp: Component> UiHandle<Comp> {
+    pub(crate) fn new(inbox: Inbox) -> Self {
+        UiHandle {
+            inbox,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sends `msg` to the root component of the window.
+    pub fn send(&self, msg: Comp::Msg)
+    where
+        Comp::Msg: Send,
+    {
+        self.inbox.push(Box::new(move |data, root| {
+            data.push_message::<Comp>(root, msg)
+        }));
+    }
+
+    /// Sends `msg` to the component behind `cid`, which has to be a `Target`.
+    pub fn send_to<Target: Component>(&self, cid: Cid, msg: Target::Msg)
+    where
+        Target::Msg: Send,
+    {
+        self.inbox.push(Box::new(move |data, _| {
+            data.push_message::<Target>(cid, msg)
+        }));
+    }
+}
+
+/// The queue behind all `UiHandle`s of a window.
+#[derive(Clone, Default)]
+pub(crate) struct Inbox {
+    shared: Arc<Mutex<InboxData>>,
+}
+
+#[derive(Default)]
+struct InboxData {
+    queue: Vec<Delivery>,
+    proxy: Option<winit::EventsLoopProxy>,
+}
+
+impl Inbox {
+    fn push(&self, delivery: Delivery) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.queue.push(delivery);
+        if let Some(proxy) = &shared.proxy {
+            let _ = proxy.wakeup();
+        }
+    }
+
+    /// Wakes the event loop behind `proxy` whenever a message arrives.
+    pub(crate) fn connect(&self, proxy: winit::EventsLoopProxy) {
+        let mut shared = self.shared.lock().unwrap();
+        if !shared.queue.is_empty() {
+            let _ = proxy.wakeup();
+        }
+        shared.proxy = Some(proxy);
+    }
+
+    /// Delivers all queued messages, returns whether there were any.
+    ///
+    /// Nothing is delivered until the `root` has been initialized.
+    pub(crate) fn deliver(&self, data: &mut UiData, root: Cid) -> bool {
+        if data.is_fresh(root) {
+            return false;
+        }
+        let queue = std::mem::take(&mut self.shared.lock().unwrap().queue);
+        let delivered = !queue.is_empty();
+        for delivery in queue {
+            delivery(data, root);
+        }
+        delivered
+    }
+
+    /// Wakes the event loop again if messages are still waiting.
+    pub(crate) fn wake_if_pending(&self) {
+        let shared = self.shared.lock().unwrap();
+        if let (false, Some(proxy)) = (shared.queue.is_empty(), &shared.proxy) {
+            let _ = proxy.wakeup();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::headless::DisplayList;
+    use crate::*;
+
+    #[derive(Default)]
+    struct Counter;
+
+    impl Component for Counter {
+        type State = u32;
+        type Msg = u32;
+        type Event = ();
+
+        fn init(_props: &Self) -> Self::State {
+            0
+        }
+
+        fn update(msg: Self::Msg, mut state: Mut<Self::State>, _ui: &mut UiUpdate) {
+            *state += msg;
+        }
+
+        fn view(_props: &Self, _state: &Self::State, _ui: &mut UiView<Self>) {}
+    }
+
+    #[test]
+    fn test_delivers_messages_from_other_threads() {
+        let mut data = UiData::default();
+        let mut renderer = DisplayList::default();
+        let root = data.fresh_id();
+        let inbox = Inbox::default();
+
+        let handle = UiHandle::<Counter>::new(inbox.clone());
+        std::thread::spawn(move || handle.send(3)).join().unwrap();
+        assert!(!inbox.deliver(&mut data, root));
+
+        UiView::<Counter>::run(&mut data, &mut renderer, root, Counter);
+        assert!(inbox.deliver(&mut data, root));
+        assert!(UiUpdate::run(&mut data, &mut renderer, root));
+
+        let state = data.state[root.get()].as_ref().unwrap();
+        assert_eq!(state.downcast_ref::<u32>(), Some(&3));
+        assert!(!inbox.deliver(&mut data, root));
+    }
+}
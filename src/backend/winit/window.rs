@@ -1,17 +1,23 @@
-use crate::backend::webrender::Webrenderer;
-use crate::backend::winit::{EventHandler, EventLoop};
-use crate::{Component, Font, Input, Size, UiData, UiInput, UiLayout, UiRender, UiUpdate, UiView};
-use gleam::gl;
-use glutin::GlContext;
-
-pub enum AppEvent {
-    SetTitle(String),
-    Quit,
-}
+use crate::backend::winit::{AppEvent, Application, EventLoop, Inbox, RenderBackend, UiHandle};
+use crate::{Cid, Component, Input, Position, Renderer, Size, UiData, UiInput, UiView};
+use std::time::Duration;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Window {
     title: String,
-    size: Size,
+    pub(crate) size: Size,
+    min_size: Option<Size>,
+    max_size: Option<Size>,
+    position: Option<Position>,
+    resizable: bool,
+    decorations: bool,
+    transparent: bool,
+    always_on_top: bool,
+    maximized: bool,
+    pub(crate) fullscreen: bool,
+    icon: Option<(Vec<u8>, u32, u32)>,
+    pub(crate) frame_interval: Duration,
+    pub(crate) renderer: RenderBackend,
 }
 
 impl Window {
@@ -19,6 +25,18 @@ impl Window {
         Window {
             title: String::new(),
             size: Size::new(600.0, 400.0),
+            min_size: None,
+            max_size: None,
+            position: None,
+            resizable: true,
+            decorations: true,
+            transparent: false,
+            always_on_top: false,
+            maximized: false,
+            fullscreen: false,
+            icon: None,
+            frame_interval: Duration::from_millis(16),
+            renderer: RenderBackend::Webrender,
         }
     }
 
@@ -27,104 +45,210 @@ impl Window {
         self
     }
 
-    pub fn run<Comp>(mut self)
+    /// The initial inner size of the window.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn min_size(mut self, size: Size) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    pub fn max_size(mut self, size: Size) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// The initial position of the window on the screen.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    pub fn maximized(mut self, maximized: bool) -> Self {
+        self.maximized = maximized;
+        self
+    }
+
+    /// Whether the window starts fullscreen on the primary monitor.
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+
+    /// Sets the window icon from `width * height` RGBA pixels.
+    pub fn icon(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        self.icon = Some((rgba, width, height));
+        self
+    }
+
+    /// The amount of frames per second this window aims for, 60 by default.
+    pub fn frame_rate(mut self, fps: u32) -> Self {
+        self.frame_interval = Duration::from_secs(1) / fps.max(1);
+        self
+    }
+
+    /// Which `Renderer` draws the contents of this window, webrender by default.
+    pub fn renderer(mut self, renderer: RenderBackend) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// Pairs this window with the root component it displays.
+    ///
+    /// The returned handle sends messages to `Comp` once the window is open.
+    pub fn root<Comp>(self) -> (NewWindow, UiHandle<Comp>)
+    where
+        Comp: Component<Event = AppEvent>,
+    {
+        let inbox = Inbox::default();
+        let window = NewWindow {
+            window: self,
+            root: RootPointer::of::<Comp>(),
+            inbox: inbox.clone(),
+        };
+        (window, UiHandle::new(inbox))
+    }
+
+    /// Opens this window as the only window of a new `Application`.
+    pub fn run<Comp>(self)
     where
         Comp: Component<Event = AppEvent>,
     {
-        let mut eventloop = EventLoop::new();
+        let (window, _) = self.root::<Comp>();
+        Application::new().window(window).run();
+    }
 
+    pub(crate) fn build(&mut self, eventloop: &EventLoop) -> glutin::GlWindow {
         let context_builder =
             glutin::ContextBuilder::new().with_gl(glutin::GlRequest::GlThenGles {
                 opengl_version: (3, 2),
                 opengles_version: (3, 0),
             });
 
-        let window_builder = winit::WindowBuilder::new()
-            .with_title(self.title)
+        let icon = self.icon.take().and_then(|(rgba, width, height)| {
+            winit::Icon::from_rgba(rgba, width, height)
+                .map_err(|err| log::error!("Invalid window icon: {:?}", err))
+                .ok()
+        });
+
+        let monitor = if self.fullscreen {
+            Some(eventloop.events_loop().get_primary_monitor())
+        } else {
+            None
+        };
+
+        let mut window_builder = winit::WindowBuilder::new()
+            .with_title(self.title.clone())
             .with_multitouch()
-            .with_dimensions(winit::dpi::LogicalSize::new(
-                self.size.width as f64,
-                self.size.height as f64,
-            ));
+            .with_dimensions(to_logical_size(self.size))
+            .with_resizable(self.resizable)
+            .with_decorations(self.decorations)
+            .with_transparency(self.transparent)
+            .with_always_on_top(self.always_on_top)
+            .with_maximized(self.maximized)
+            .with_fullscreen(monitor)
+            .with_window_icon(icon);
+
+        if let Some(size) = self.min_size {
+            window_builder = window_builder.with_min_dimensions(to_logical_size(size));
+        }
+        if let Some(size) = self.max_size {
+            window_builder = window_builder.with_max_dimensions(to_logical_size(size));
+        }
 
         let window =
             glutin::GlWindow::new(window_builder, context_builder, eventloop.events_loop())
                 .unwrap();
 
-        unsafe {
-            window.make_current().ok();
+        // winit 0.18 can't position a window before it is created
+        if let Some(position) = self.position {
+            window.set_position(winit::dpi::LogicalPosition::new(
+                position.x as f64,
+                position.y as f64,
+            ));
         }
 
-        let gl = match window.get_api() {
-            glutin::Api::OpenGl => unsafe {
-                gl::GlFns::load_with(|symbol| window.get_proc_address(symbol) as *const _)
-            },
-            glutin::Api::OpenGlEs => unsafe {
-                gl::GlesFns::load_with(|symbol| window.get_proc_address(symbol) as *const _)
-            },
-            glutin::Api::WebGl => unimplemented!(),
-        };
+        window
+    }
+}
 
-        let mut dpr = window.get_hidpi_factor() as f32;
-
-        let mut input = Input::new();
-        let mut ehandler = EventHandler::new();
-        let mut data = UiData::default();
-        let app_id = data.fresh_id();
-        let mut resized = false;
-
-        let mut renderer = Webrenderer::new(eventloop.create_proxy(), gl.clone(), dpr);
-        // TODO: pass `Size` directly
-        renderer.resize(self.size, dpr);
-        renderer.add_font(
-            Font::from_family(super::DEFAULT_FONT_NAME),
-            super::DEFAULT_FONT,
-        );
-
-        'main: loop {
-            let events = eventloop.next();
-
-            for event in events {
-                use winit::{Event, WindowEvent};
-                match event {
-                    Event::WindowEvent { ref event, .. } => match event {
-                        WindowEvent::CloseRequested => {
-                            break 'main;
-                        }
-                        WindowEvent::Resized(lsize) => {
-                            self.size = Size::new(lsize.width as f32, lsize.height as f32);
-                            renderer.resize(self.size, dpr);
-                            resized = true;
-                        }
-                        WindowEvent::HiDpiFactorChanged(new_dpr) => {
-                            dpr = (*new_dpr) as f32;
-                            renderer.resize(self.size, dpr);
-                        }
-                        _ => (),
-                    },
-                    _ => (),
-                }
-                if let Some(event) = ehandler.convert_winit_event(event) {
-                    input.push_event(event);
-                }
-            }
-
-            // This is where everything happens!
-            if UiInput::<Comp>::run(&mut data, &mut input, app_id) || resized {
-                if UiUpdate::run(&mut data, &mut renderer, app_id) || resized {
-                    UiView::<Comp>::run(&mut data, &mut renderer, app_id, Comp::default());
-                    UiLayout::run(&mut data, app_id, self.size);
-                    UiRender::run(&data, &mut renderer, app_id);
-                    renderer.render();
-                }
-            }
-
-            resized = false;
-            input.clear_events();
-            renderer.flush();
-            window.swap_buffers().ok();
+/// A `Window` paired with its root component, see `Window::root`.
+pub struct NewWindow {
+    pub(crate) window: Window,
+    pub(crate) root: RootPointer,
+    pub(crate) inbox: Inbox,
+}
+
+/// A clone opens a window of its own, so the handles of the original do not reach it.
+impl Clone for NewWindow {
+    fn clone(&self) -> Self {
+        NewWindow {
+            window: self.window.clone(),
+            root: self.root,
+            inbox: Inbox::default(),
         }
+    }
+}
+
+impl std::fmt::Debug for NewWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("NewWindow")
+            .field("window", &self.window)
+            .field("root", &self.root.name)
+            .finish()
+    }
+}
+
+impl PartialEq for NewWindow {
+    fn eq(&self, other: &Self) -> bool {
+        self.window == other.window && self.root.name == other.root.name
+    }
+}
+
+/// Runs the generic parts of the pipeline for a root component.
+#[derive(Clone, Copy)]
+pub(crate) struct RootPointer {
+    pub name: &'static str,
+    pub input: fn(&mut UiData, &mut Input, Cid) -> bool,
+    pub view: fn(&mut UiData, &mut dyn Renderer, Cid),
+}
 
-        renderer.deinit();
+impl RootPointer {
+    fn of<Comp>() -> Self
+    where
+        Comp: Component<Event = AppEvent>,
+    {
+        RootPointer {
+            name: std::any::type_name::<Comp>(),
+            input: |data, input, root| UiInput::<Comp>::run(data, input, root),
+            view: |data, renderer, root| UiView::<Comp>::run(data, renderer, root, Comp::default()),
+        }
     }
 }
+
+pub(crate) fn to_logical_size(size: Size) -> winit::dpi::LogicalSize {
+    winit::dpi::LogicalSize::new(size.width as f64, size.height as f64)
+}
@@ -0,0 +1,217 @@
+use crate::backend::headless::HeadlessRenderer;
+use crate::backend::software::SoftwareRenderer;
+use crate::backend::webrender::Webrenderer;
+use crate::{Bitmap, Bounds, Color, Font, FontManager, FontSize, LayoutGlyph, Renderer, Size};
+use gleam::gl;
+use std::rc::Rc;
+
+/// Which `Renderer` draws the contents of a `Window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderBackend {
+    /// Renders on the GPU using webrender, the default.
+    Webrender,
+    /// Rasterizes everything on the CPU using the `SoftwareRenderer`,
+    /// GL is then only used to show the finished frames.
+    Software,
+}
+
+/// The renderer of an open window, as chosen by `Window::renderer`.
+pub(crate) enum WindowRenderer {
+    Webrender(Webrenderer),
+    Software(SoftwarePresenter),
+}
+
+impl WindowRenderer {
+    pub(crate) fn new(
+        backend: RenderBackend,
+        proxy: winit::EventsLoopProxy,
+        gl: Rc<dyn gl::Gl>,
+        dpr: f32,
+    ) -> Self {
+        match backend {
+            RenderBackend::Webrender => WindowRenderer::Webrender(Webrenderer::new(proxy, gl, dpr)),
+            RenderBackend::Software => WindowRenderer::Software(SoftwarePresenter::new(gl, dpr)),
+        }
+    }
+
+    fn inner(&self) -> &dyn Renderer {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer,
+            WindowRenderer::Software(presenter) => &presenter.renderer,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn Renderer {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer,
+            WindowRenderer::Software(presenter) => &mut presenter.renderer,
+        }
+    }
+
+    pub(crate) fn resize(&mut self, size: Size, dpr: f32) {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer.resize(size, dpr),
+            WindowRenderer::Software(presenter) => {
+                presenter.size = size;
+                presenter.dpr = dpr;
+            }
+        }
+    }
+
+    /// Has to be called right before `UiRender` draws the next frame.
+    pub(crate) fn begin_frame(&mut self) {
+        if let WindowRenderer::Software(presenter) = self {
+            presenter.renderer.begin_frame(presenter.size);
+        }
+    }
+
+    /// Finishes the frame that has been drawn since `begin_frame`.
+    pub(crate) fn render(&mut self) {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer.render(),
+            WindowRenderer::Software(presenter) => presenter.dirty = true,
+        }
+    }
+
+    /// Draws the last frame into the current GL context.
+    pub(crate) fn flush(&mut self) {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer.flush(),
+            WindowRenderer::Software(presenter) => presenter.flush(),
+        }
+    }
+
+    pub(crate) fn deinit(self) {
+        match self {
+            WindowRenderer::Webrender(renderer) => renderer.deinit(),
+            WindowRenderer::Software(presenter) => presenter.deinit(),
+        }
+    }
+}
+
+impl Renderer for WindowRenderer {
+    fn fonts(&self) -> &FontManager {
+        self.inner().fonts()
+    }
+
+    fn add_font(&mut self, font: Font, data: Vec<u8>) {
+        self.inner_mut().add_font(font, data);
+    }
+
+    fn remove_font(&mut self, font: &Font) {
+        self.inner_mut().remove_font(font);
+    }
+
+    fn rect(&mut self, bounds: Bounds, color: Color) {
+        self.inner_mut().rect(bounds, color);
+    }
+
+    fn glyphs(
+        &mut self,
+        bounds: Bounds,
+        font: &Font,
+        size: FontSize,
+        color: Color,
+        glyphs: &[LayoutGlyph],
+    ) {
+        self.inner_mut().glyphs(bounds, font, size, color, glyphs);
+    }
+
+    fn image(&mut self, bounds: Bounds, bitmap: &Bitmap) {
+        self.inner_mut().image(bounds, bitmap);
+    }
+
+    fn push_clip(&mut self, bounds: Bounds) {
+        self.inner_mut().push_clip(bounds);
+    }
+
+    fn pop_clip(&mut self) {
+        self.inner_mut().pop_clip();
+    }
+}
+
+/// Shows the frames of a `SoftwareRenderer` by copying them into the window.
+pub(crate) struct SoftwarePresenter {
+    renderer: SoftwareRenderer,
+    gl: Rc<dyn gl::Gl>,
+    texture: gl::GLuint,
+    framebuffer: gl::GLuint,
+    size: Size,
+    dpr: f32,
+    /// Whether the frame buffer changed since it has been uploaded
+    dirty: bool,
+}
+
+impl SoftwarePresenter {
+    fn new(gl: Rc<dyn gl::Gl>, dpr: f32) -> Self {
+        let texture = gl.gen_textures(1)[0];
+        gl.bind_texture(gl::TEXTURE_2D, texture);
+        gl.tex_parameter_i(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+        let framebuffer = gl.gen_framebuffers(1)[0];
+        SoftwarePresenter {
+            renderer: SoftwareRenderer::default(),
+            gl,
+            texture,
+            framebuffer,
+            size: Size::zero(),
+            dpr,
+            dirty: false,
+        }
+    }
+
+    fn flush(&mut self) {
+        let (width, height) = (self.renderer.width(), self.renderer.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (width, height) = (width as i32, height as i32);
+        let gl = &self.gl;
+
+        gl.bind_framebuffer(gl::READ_FRAMEBUFFER, self.framebuffer);
+        if self.dirty {
+            gl.bind_texture(gl::TEXTURE_2D, self.texture);
+            gl.tex_image_2d(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA8 as i32,
+                width,
+                height,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                Some(self.renderer.pixels()),
+            );
+            gl.framebuffer_texture_2d(
+                gl::READ_FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                self.texture,
+                0,
+            );
+            self.dirty = false;
+        }
+
+        // The rows of the frame buffer go from top to bottom, those of GL the other way around
+        let device_width = (self.size.width * self.dpr).round() as i32;
+        let device_height = (self.size.height * self.dpr).round() as i32;
+        gl.bind_framebuffer(gl::DRAW_FRAMEBUFFER, 0);
+        gl.blit_framebuffer(
+            0,
+            0,
+            width,
+            height,
+            0,
+            device_height,
+            device_width,
+            0,
+            gl::COLOR_BUFFER_BIT,
+            gl::LINEAR,
+        );
+        gl.bind_framebuffer(gl::READ_FRAMEBUFFER, 0);
+    }
+
+    fn deinit(self) {
+        self.gl.delete_framebuffers(&[self.framebuffer]);
+        self.gl.delete_textures(&[self.texture]);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Size;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static NEXT_BITMAP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// An image made of RGBA pixels with straight alpha, stored row by row.
+///
+/// Cloning a `Bitmap` is cheap as the pixels are shared,
+/// which also allows renderers to upload them only once.
+#[derive(Debug, Clone)]
+pub struct Bitmap {
+    id: usize,
+    width: u32,
+    height: u32,
+    pixels: Arc<Vec<u8>>,
+}
+
+impl Bitmap {
+    pub fn from_rgba(width: u32, height: u32, pixels: impl Into<Vec<u8>>) -> Self {
+        let mut pixels = pixels.into();
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            log::error!(
+                "A {}x{} `Bitmap` needs {} bytes but {} were given",
+                width,
+                height,
+                expected,
+                pixels.len()
+            );
+            pixels.resize(expected, 0);
+        }
+
+        Bitmap {
+            id: NEXT_BITMAP_ID.fetch_add(1, Ordering::Relaxed),
+            width,
+            height,
+            pixels: Arc::new(pixels),
+        }
+    }
+
+    /// Identifies the pixels of this `Bitmap`, it is shared by all clones.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width as f32, self.height as f32)
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+}
+
+impl PartialEq for Bitmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
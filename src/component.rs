@@ -1,6 +1,6 @@
 use crate::{
-    Bounds, BoxConstraints, Cid, ContentBuilder, Iid, Mut, Renderer, Size, UiDerive, UiInput,
-    UiInputBase, UiLayout, UiUpdate, UiView,
+    Bounds, BoxConstraints, Cid, ContentBuilder, Iid, Mut, Renderer, Scalar, Size, UiDerive,
+    UiInput, UiInputBase, UiLayout, UiUpdate, UiView,
 };
 use std::any::Any;
 
@@ -29,6 +29,12 @@ pub trait Component: Default + Sized {
     #[allow(unused_variables)]
     fn update(msg: Self::Msg, state: Mut<Self::State>, ui: &mut UiUpdate) {}
 
+    /// Advances animations at the beginning of a frame, see `UiUpdate::request_tick`.
+    ///
+    /// It runs before the messages of the frame are handed to `update`.
+    #[allow(unused_variables)]
+    fn tick(state: Mut<Self::State>, ui: &mut UiUpdate) {}
+
     #[allow(unused_variables)]
     fn view(props: &Self, state: &Self::State, ui: &mut UiView<Self>) {}
 
@@ -55,19 +61,35 @@ pub trait Component: Default + Sized {
     fn input(state: &Self::State, input: &mut UiInput<Self>) {}
 
     #[allow(unused_variables)]
-    fn render(state: &Self::State, bounds: Bounds, renderer: &mut Renderer) {}
+    fn render(state: &Self::State, bounds: Bounds, renderer: &mut dyn Renderer) {}
+
+    /// Restricts the rendering of all children to the returned `Bounds`.
+    #[allow(unused_variables)]
+    fn clip(state: &Self::State, bounds: Bounds) -> Option<Bounds> {
+        None
+    }
+
+    /// The distance from the top of the component to the baseline of its text.
+    /// By default this is the baseline of the first child, see `UiLayout::baseline`.
+    #[allow(unused_variables)]
+    fn baseline(state: &Self::State) -> Option<Scalar> {
+        None
+    }
 }
 
 pub(crate) trait ComponentPointerTrait: Component {
     fn pointer() -> ComponentPointer;
     fn dyn_update(messages: &mut Box<Any>, state: &mut Box<Any>, ui: &mut UiUpdate);
+    fn dyn_tick(state: &mut Box<dyn Any>, ui: &mut UiUpdate);
     fn dyn_layout(
         state: &Box<Any>,
         children: &[Cid],
         constraints: BoxConstraints,
         ui: &mut UiLayout,
     ) -> Size;
-    fn dyn_render(state: &Box<Any>, bounds: Bounds, renderer: &mut Renderer);
+    fn dyn_render(state: &Box<Any>, bounds: Bounds, renderer: &mut dyn Renderer);
+    fn dyn_clip(state: &Box<dyn Any>, bounds: Bounds) -> Option<Bounds>;
+    fn dyn_baseline(state: &Box<dyn Any>) -> Option<Scalar>;
     fn dyn_input(input: &mut UiInputBase);
 }
 
@@ -77,10 +99,14 @@ where
 {
     fn pointer() -> ComponentPointer {
         ComponentPointer {
+            type_name: std::any::type_name::<Self>(),
             layout: Self::dyn_layout,
             render: Self::dyn_render,
+            clip: Self::dyn_clip,
+            baseline: Self::dyn_baseline,
             input: Self::dyn_input,
             update: Self::dyn_update,
+            tick: Self::dyn_tick,
         }
     }
 
@@ -97,6 +123,15 @@ where
         }
     }
 
+    fn dyn_tick(state: &mut Box<dyn Any>, ui: &mut UiUpdate) {
+        let state: &mut Self::State = state.downcast_mut().unwrap();
+        let mut mutated = false;
+        Self::tick(Mut::new(state, &mut mutated), ui);
+        if mutated {
+            ui.needs_update();
+        }
+    }
+
     fn dyn_layout(
         state: &Box<Any>,
         children: &[Cid],
@@ -107,11 +142,21 @@ where
         Self::layout(state, children, constraints, ui)
     }
 
-    fn dyn_render(state: &Box<Any>, bounds: Bounds, renderer: &mut Renderer) {
+    fn dyn_render(state: &Box<Any>, bounds: Bounds, renderer: &mut dyn Renderer) {
         let state: &Self::State = state.downcast_ref().unwrap();
         Self::render(state, bounds, renderer);
     }
 
+    fn dyn_clip(state: &Box<dyn Any>, bounds: Bounds) -> Option<Bounds> {
+        let state: &Self::State = state.downcast_ref().unwrap();
+        Self::clip(state, bounds)
+    }
+
+    fn dyn_baseline(state: &Box<dyn Any>) -> Option<Scalar> {
+        let state: &Self::State = state.downcast_ref().unwrap();
+        Self::baseline(state)
+    }
+
     fn dyn_input(base: &mut UiInputBase) {
         let state: &Box<Any> = base.state[base.cid.get()].as_ref().unwrap();
         let state: &Self::State = state.downcast_ref().unwrap();
@@ -125,25 +170,34 @@ where
 
 #[derive(Clone, Copy)]
 pub(crate) struct ComponentPointer {
+    /// The full path of the `Component`s type, including its generics
+    pub type_name: &'static str,
     pub layout: fn(
         state: &Box<Any>,
         children: &[Cid],
         constraints: BoxConstraints,
         ui: &mut UiLayout,
     ) -> Size,
-    pub render: fn(state: &Box<Any>, bounds: Bounds, renderer: &mut Renderer),
+    pub render: fn(state: &Box<Any>, bounds: Bounds, renderer: &mut dyn Renderer),
+    pub clip: fn(state: &Box<dyn Any>, bounds: Bounds) -> Option<Bounds>,
+    pub baseline: fn(state: &Box<dyn Any>) -> Option<Scalar>,
     pub input: fn(input: &mut UiInputBase),
     pub update: fn(messages: &mut Box<Any>, state: &mut Box<Any>, ui: &mut UiUpdate),
+    pub tick: fn(state: &mut Box<dyn Any>, ui: &mut UiUpdate),
 }
 
 impl Default for ComponentPointer {
     #[rustfmt::skip]
     fn default() -> Self {
         ComponentPointer {
+            type_name: "",
             layout: |_, _, _, _| panic!("Called `layout` on default `ComponentPointer`"),
             render: |_, _, _|    panic!("Called `render` on default `ComponentPointer`"),
+            clip  : |_, _|       panic!("Called `clip` on default `ComponentPointer`"  ),
+            baseline: |_|        panic!("Called `baseline` on default `ComponentPointer`"),
             input : |_|          panic!("Called `input` on default `ComponentPointer`" ),
             update: |_, _, _|    panic!("Called `update` on default `ComponentPointer`"),
+            tick  : |_, _|       panic!("Called `tick` on default `ComponentPointer`"  ),
         }
     }
 }
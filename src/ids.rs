@@ -1,3 +1,6 @@
+use fnv::FnvHasher;
+use std::hash::{Hash, Hasher};
+
 /// A "Component Identifier"
 ///
 /// This is the id used to reference a components data
@@ -5,20 +8,32 @@
 ///
 /// It can be different every time an app runs and gets
 /// generated at runtime.
+///
+/// The slots in `UiData` are being reused after a component has been removed,
+/// so besides the index a `Cid` also stores the generation of its slot.
+/// This allows to detect stale `Cid`s instead of silently aliasing
+/// the component that took over the slot.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Cid(usize);
+pub struct Cid {
+    index: usize,
+    generation: u32,
+}
 
 impl Cid {
-    pub(crate) fn new(id: usize) -> Self {
-        Cid(id)
+    pub(crate) fn new(index: usize, generation: u32) -> Self {
+        Cid { index, generation }
     }
 
     pub(crate) fn invalid() -> Self {
-        Cid(std::usize::MAX)
+        Cid::new(std::usize::MAX, 0)
     }
 
     pub(crate) fn get(&self) -> usize {
-        self.0
+        self.index
+    }
+
+    pub(crate) fn generation(&self) -> u32 {
+        self.generation
     }
 }
 
@@ -43,8 +58,12 @@ impl Cid {
 pub struct Iid {
     pub(crate) name: Option<&'static str>,
     pub(crate) id: IidSecret,
+    pub(crate) key: Option<u64>,
 }
-pub(crate) type IidSecret = (u32, u32);
+/// The source file, line and column of the `Iid`s declaration
+pub(crate) type IidSecret = (&'static str, u32, u32);
+/// The `IidSecret` combined with the runtime key of an `Iid`
+pub(crate) type IidKey = (IidSecret, Option<u64>);
 
 impl Iid {
     /// **Don't use this**
@@ -52,19 +71,47 @@ impl Iid {
     /// `Iid`s should be created using `iids!()` and `iid()` but this has to
     /// be public to make the macros work from other crates
     pub const fn new(name: Option<&'static str>, id: IidSecret) -> Self {
-        Iid { name, id }
+        Iid {
+            name,
+            id,
+            key: None,
+        }
+    }
+
+    /// Combines this `Iid` with a key that is only known at runtime.
+    ///
+    /// This is necessary whenever components are being `set` in a loop,
+    /// as all of them would share the same `Iid` otherwise.
+    ///
+    /// ```ignore
+    /// for (index, option) in props.options.iter().enumerate() {
+    ///     Button::new()
+    ///         .label(option)
+    ///         .set(iid!(Option).key(option), ui);
+    /// }
+    /// ```
+    pub fn key<K: Hash>(mut self, key: K) -> Self {
+        let mut hasher = FnvHasher::default();
+        self.key.hash(&mut hasher);
+        key.hash(&mut hasher);
+        self.key = Some(hasher.finish());
+        self
+    }
+
+    pub(crate) fn combined(&self) -> IidKey {
+        (self.id, self.key)
     }
 }
 
 impl PartialEq for Iid {
     fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+        self.combined() == other.combined()
     }
 }
 
-impl std::hash::Hash for Iid {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
+impl Hash for Iid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.combined().hash(state);
     }
 }
 
@@ -85,10 +132,13 @@ macro_rules! iid {
         iid!(Unnamed)
     };
     ($id:ident) => {
-        $crate::Iid::new(Some(stringify!($id)), (line!(), column!()))
+        $crate::Iid::new(Some(stringify!($id)), (file!(), line!(), column!()))
     };
     ($id:ident; $offset:expr) => {
-        $crate::Iid::new(Some(stringify!($id)), (line!(), column!() + $offset))
+        $crate::Iid::new(
+            Some(stringify!($id)),
+            (file!(), line!(), column!() + $offset),
+        )
     };
 }
 
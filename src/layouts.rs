@@ -1,14 +1,56 @@
+use crate::{BoxConstraints, Cid, Position, Size, UiLayout};
+
 mod linear;
-pub use self::linear::Linear;
+pub use self::linear::{CrossAxisAlignment, Linear, MainAxisAlignment};
+
+mod flexible;
+pub use self::flexible::{Expanded, FlexFit, Flexible};
 
 mod constrained;
 pub use self::constrained::Constrained;
 
+mod grid;
+pub use self::grid::{Grid, GridItem, Track};
+
+mod align;
+pub use self::align::{Align, Alignment, Center};
+
 mod stack;
-pub use self::stack::Stack;
+pub use self::stack::{Aligned, Positioned, Stack, StackFit};
 
 mod padding;
 pub use self::padding::Padding;
 
 mod offset;
 pub use self::offset::Offset;
+
+mod animated_padding;
+pub use self::animated_padding::AnimatedPadding;
+
+mod animated_offset;
+pub use self::animated_offset::AnimatedOffset;
+
+/// Lays out the only child of the wrapper component `name` at its own origin,
+/// as done by the components that only carry settings for their parent layout.
+pub(crate) fn layout_single_child(
+    name: &str,
+    children: &[Cid],
+    constraints: BoxConstraints,
+    ui: &mut UiLayout,
+) -> Size {
+    if children.len() != 1 {
+        log::error!(
+            "`{}` layout component {} must have exactly 1 child but it has {}",
+            name,
+            ui.full_debug_name(),
+            children.len(),
+        );
+        if children.is_empty() {
+            return Size::zero();
+        }
+    }
+
+    let size = ui.size(children[0], constraints);
+    ui.position(children[0], Position::zero());
+    size
+}
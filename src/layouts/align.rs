@@ -0,0 +1,163 @@
+use crate::*;
+
+/// A point within a rectangle, from `-1.0` at the top / left edge
+/// to `1.0` at the bottom / right edge.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Alignment {
+    pub x: Scalar,
+    pub y: Scalar,
+}
+
+impl Alignment {
+    pub const TOP_LEFT: Alignment = Alignment::new(-1.0, -1.0);
+    pub const TOP_CENTER: Alignment = Alignment::new(0.0, -1.0);
+    pub const TOP_RIGHT: Alignment = Alignment::new(1.0, -1.0);
+    pub const CENTER_LEFT: Alignment = Alignment::new(-1.0, 0.0);
+    pub const CENTER: Alignment = Alignment::new(0.0, 0.0);
+    pub const CENTER_RIGHT: Alignment = Alignment::new(1.0, 0.0);
+    pub const BOTTOM_LEFT: Alignment = Alignment::new(-1.0, 1.0);
+    pub const BOTTOM_CENTER: Alignment = Alignment::new(0.0, 1.0);
+    pub const BOTTOM_RIGHT: Alignment = Alignment::new(1.0, 1.0);
+
+    pub const fn new(x: Scalar, y: Scalar) -> Self {
+        Alignment { x, y }
+    }
+
+    /// The position of something of size `inner` aligned within `outer`.
+    pub fn within(self, outer: Size, inner: Size) -> Position {
+        let x = (outer.width - inner.width) * (self.x + 1.0) / 2.0;
+        let y = (outer.height - inner.height) * (self.y + 1.0) / 2.0;
+        Position::new(x, y)
+    }
+}
+
+/// Aligns its child within itself.
+///
+/// `Align` is as large as its constraints allow, unless a size factor is given.
+/// Then it is that many times as large as its child in the respective direction.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Align {
+    alignment: Alignment,
+    width_factor: Option<Scalar>,
+    height_factor: Option<Scalar>,
+}
+
+impl Align {
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn width_factor(mut self, factor: Scalar) -> Self {
+        self.width_factor = Some(factor);
+        self
+    }
+
+    pub fn height_factor(mut self, factor: Scalar) -> Self {
+        self.height_factor = Some(factor);
+        self
+    }
+}
+
+impl Component for Align {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self, _ui: &UiDerive) {
+        if *props != *state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        state: &Self,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        if children.len() != 1 {
+            log::error!(
+                "`Align` layout component {} must have exactly 1 child but it has {}",
+                ui.full_debug_name(),
+                children.len(),
+            );
+            if children.is_empty() {
+                return Size::zero();
+            }
+        }
+
+        let child = children[0];
+        let size = ui.size(child, constraints.min(Size::zero()));
+
+        let width = match (state.width_factor, constraints.max_width) {
+            (None, Some(max_width)) => max_width,
+            (factor, _) => size.width * factor.unwrap_or(1.0),
+        };
+        let height = match (state.height_factor, constraints.max_height) {
+            (None, Some(max_height)) => max_height,
+            (factor, _) => size.height * factor.unwrap_or(1.0),
+        };
+        let outer = constraints.check_size(Size::new(width, height));
+
+        ui.position(child, state.alignment.within(outer, size));
+
+        outer
+    }
+}
+
+/// Centers its child within itself, this is a shorthand for `Align`.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Center {
+    width_factor: Option<Scalar>,
+    height_factor: Option<Scalar>,
+}
+
+impl Center {
+    pub fn width_factor(mut self, factor: Scalar) -> Self {
+        self.width_factor = Some(factor);
+        self
+    }
+
+    pub fn height_factor(mut self, factor: Scalar) -> Self {
+        self.height_factor = Some(factor);
+        self
+    }
+
+    fn align(self) -> Align {
+        Align {
+            alignment: Alignment::CENTER,
+            width_factor: self.width_factor,
+            height_factor: self.height_factor,
+        }
+    }
+}
+
+impl Component for Center {
+    type State = Align;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Align {
+        props.align()
+    }
+
+    fn derive_state(props: &Self, state: &mut Align, _ui: &UiDerive) {
+        if props.align() != *state {
+            *state = props.align();
+        }
+    }
+
+    fn layout(
+        state: &Align,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        Align::layout(state, children, constraints, ui)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::*;
+
+/// An `Offset` that animates to its new position whenever it changes.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct AnimatedOffset {
+    offset: Offset,
+    transition: Transition,
+}
+
+impl AnimatedOffset {
+    pub fn x(mut self, x: Scalar) -> Self {
+        self.offset.x = x;
+        self
+    }
+
+    pub fn y(mut self, y: Scalar) -> Self {
+        self.offset.y = y;
+        self
+    }
+
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    fn position(&self) -> Position {
+        Position::new(self.offset.x, self.offset.y)
+    }
+}
+
+impl Component for AnimatedOffset {
+    type State = Animated<Position>;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        Animated::new(props.position()).transition(props.transition)
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, ui: &UiDerive) {
+        state.set_transition(props.transition);
+        if props.position() != state.target() {
+            state.set(props.position(), ui.frame_time());
+            ui.request_tick();
+        }
+    }
+
+    fn tick(mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.drive(ui);
+    }
+
+    fn layout(
+        state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        let Position { x, y, .. } = state.value();
+        Offset::layout(&Offset { x, y }, children, constraints, ui)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+/// A `Padding` that animates to its new values whenever they change.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct AnimatedPadding {
+    padding: Padding,
+    transition: Transition,
+}
+
+impl AnimatedPadding {
+    pub fn all(mut self, value: Scalar) -> Self {
+        self.padding = self.padding.all(value);
+        self
+    }
+
+    pub fn top(mut self, value: Scalar) -> Self {
+        self.padding = self.padding.top(value);
+        self
+    }
+
+    pub fn right(mut self, value: Scalar) -> Self {
+        self.padding = self.padding.right(value);
+        self
+    }
+
+    pub fn bottom(mut self, value: Scalar) -> Self {
+        self.padding = self.padding.bottom(value);
+        self
+    }
+
+    pub fn left(mut self, value: Scalar) -> Self {
+        self.padding = self.padding.left(value);
+        self
+    }
+
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+}
+
+impl Animatable for Padding {
+    fn zero() -> Self {
+        Padding::default()
+    }
+
+    fn add(self, other: Self) -> Self {
+        Padding {
+            top: self.top + other.top,
+            right: self.right + other.right,
+            bottom: self.bottom + other.bottom,
+            left: self.left + other.left,
+        }
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self.add(other.scale(-1.0))
+    }
+
+    fn scale(self, factor: Scalar) -> Self {
+        Padding {
+            top: self.top * factor,
+            right: self.right * factor,
+            bottom: self.bottom * factor,
+            left: self.left * factor,
+        }
+    }
+
+    fn magnitude(self) -> Scalar {
+        self.top
+            .abs()
+            .max(self.right.abs())
+            .max(self.bottom.abs())
+            .max(self.left.abs())
+    }
+}
+
+impl Component for AnimatedPadding {
+    type State = Animated<Padding>;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        Animated::new(props.padding).transition(props.transition)
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, ui: &UiDerive) {
+        state.set_transition(props.transition);
+        if props.padding != state.target() {
+            state.set(props.padding, ui.frame_time());
+            ui.request_tick();
+        }
+    }
+
+    fn tick(mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.drive(ui);
+    }
+
+    fn layout(
+        state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        Padding::layout(&state.value(), children, constraints, ui)
+    }
+}
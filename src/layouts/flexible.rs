@@ -0,0 +1,120 @@
+use crate::layouts::layout_single_child;
+use crate::*;
+
+/// How a `Flexible` child of a `Linear` fills its share of the free space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexFit {
+    /// The child has to fill its share.
+    Tight,
+    /// The child may be smaller than its share.
+    Loose,
+}
+
+/// Gives its child a share of the space that is left in a `Linear`
+/// after all inflexible children have been sized.
+///
+/// The space is distributed proportionally to the `flex` factors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flexible {
+    pub(crate) flex: Scalar,
+    pub(crate) fit: FlexFit,
+}
+
+impl Default for Flexible {
+    fn default() -> Self {
+        Flexible {
+            flex: 1.0,
+            fit: FlexFit::Loose,
+        }
+    }
+}
+
+impl Flexible {
+    pub fn flex(mut self, flex: Scalar) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    pub fn fit(mut self, fit: FlexFit) -> Self {
+        self.fit = fit;
+        self
+    }
+}
+
+/// A `Flexible` that always fills its share, as with `FlexFit::Tight`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Expanded {
+    pub(crate) flex: Scalar,
+}
+
+impl Default for Expanded {
+    fn default() -> Self {
+        Expanded { flex: 1.0 }
+    }
+}
+
+impl Expanded {
+    pub fn flex(mut self, flex: Scalar) -> Self {
+        self.flex = flex;
+        self
+    }
+}
+
+/// The flex factor and fit of `child` if it is a `Flexible` or `Expanded`.
+pub(crate) fn flex_of(ui: &UiLayout, child: Cid) -> Option<(Scalar, FlexFit)> {
+    let flexible = ui.state::<Flexible>(child).map(|it| (it.flex, it.fit));
+    flexible.or_else(|| {
+        ui.state::<Expanded>(child)
+            .map(|it| (it.flex, FlexFit::Tight))
+    })
+}
+
+impl Component for Flexible {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props != state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        layout_single_child("Flexible", children, constraints, ui)
+    }
+}
+
+impl Component for Expanded {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props != state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        layout_single_child("Expanded", children, constraints, ui)
+    }
+}
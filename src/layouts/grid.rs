@@ -0,0 +1,401 @@
+use crate::layouts::layout_single_child;
+use crate::*;
+use fnv::FnvHashSet;
+
+/// The size of a row or column of a `Grid`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    /// A fixed size.
+    Fixed(Scalar),
+    /// A share of the space that is left after all other tracks are sized.
+    ///
+    /// In a `Grid` that is unbounded in this direction it behaves like `Auto`.
+    Fraction(Scalar),
+    /// As large as the largest child in this track.
+    Auto,
+}
+
+/// Lays out its children in rows and columns.
+///
+/// Children are placed by wrapping them in a `GridItem`. All other children
+/// fill the cells that are still free, row by row. Rows that are needed
+/// but not defined in `rows` are `Track::Auto`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Grid {
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    column_gap: Scalar,
+    row_gap: Scalar,
+}
+
+impl Grid {
+    pub fn columns(mut self, columns: &[Track]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn rows(mut self, rows: &[Track]) -> Self {
+        self.rows = rows.to_vec();
+        self
+    }
+
+    /// Sets both the column and the row gap.
+    pub fn gap(self, gap: Scalar) -> Self {
+        self.column_gap(gap).row_gap(gap)
+    }
+
+    pub fn column_gap(mut self, gap: Scalar) -> Self {
+        self.column_gap = gap;
+        self
+    }
+
+    pub fn row_gap(mut self, gap: Scalar) -> Self {
+        self.row_gap = gap;
+        self
+    }
+}
+
+/// Places its child in a specific cell of a `Grid`, optionally spanning multiple tracks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridItem {
+    pub(crate) row: usize,
+    pub(crate) column: usize,
+    pub(crate) row_span: usize,
+    pub(crate) column_span: usize,
+}
+
+impl Default for GridItem {
+    fn default() -> Self {
+        GridItem {
+            row: 0,
+            column: 0,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+}
+
+impl GridItem {
+    pub fn row(mut self, row: usize) -> Self {
+        self.row = row;
+        self
+    }
+
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = column;
+        self
+    }
+
+    pub fn row_span(mut self, span: usize) -> Self {
+        self.row_span = span.max(1);
+        self
+    }
+
+    pub fn column_span(mut self, span: usize) -> Self {
+        self.column_span = span.max(1);
+        self
+    }
+}
+
+/// Where a child ended up in the `Grid`.
+struct Cell {
+    child: Cid,
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+/// Assigns every child a cell, either from its `GridItem` or the next free one.
+fn place(children: &[Cid], columns: usize, ui: &UiLayout) -> Vec<Cell> {
+    let mut cells = Vec::with_capacity(children.len());
+    let mut occupied = FnvHashSet::default();
+
+    for child in children.iter().cloned() {
+        if let Some(item) = ui.state::<GridItem>(child) {
+            let mut column = item.column;
+            let mut column_span = item.column_span;
+            if column + column_span > columns {
+                log::error!(
+                    "Child {:?} of `Grid` layout {} spans beyond the last column",
+                    child,
+                    ui.full_debug_name(),
+                );
+                column = column.min(columns - 1);
+                column_span = column_span.min(columns - column);
+            }
+            for row in item.row..item.row + item.row_span {
+                for column in column..column + column_span {
+                    occupied.insert((row, column));
+                }
+            }
+            cells.push(Cell {
+                child,
+                row: item.row,
+                column,
+                row_span: item.row_span,
+                column_span,
+            });
+        }
+    }
+
+    let mut next = 0;
+    for child in children.iter().cloned() {
+        if ui.state::<GridItem>(child).is_none() {
+            while occupied.contains(&(next / columns, next % columns)) {
+                next += 1;
+            }
+            cells.push(Cell {
+                child,
+                row: next / columns,
+                column: next % columns,
+                row_span: 1,
+                column_span: 1,
+            });
+            next += 1;
+        }
+    }
+
+    cells
+}
+
+/// Whether the size of `track` depends on its content.
+fn is_auto(track: Track, available: Option<Scalar>) -> bool {
+    match track {
+        Track::Auto => true,
+        Track::Fraction(_) => available.is_none(),
+        Track::Fixed(_) => false,
+    }
+}
+
+/// The sizes of `tracks`, given the content size of the children spanning
+/// the tracks `start..start + span` as `(start, span, content)`.
+fn track_sizes(
+    tracks: &[Track],
+    gap: Scalar,
+    available: Option<Scalar>,
+    contents: &[(usize, usize, Scalar)],
+) -> Vec<Scalar> {
+    let mut sizes: Vec<Scalar> = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fixed(size) => *size,
+            _ => 0.0,
+        })
+        .collect();
+
+    // Children in a single track are considered first, so spanning ones
+    // only have to make up for what is still missing.
+    let mut contents = contents.to_vec();
+    contents.sort_by_key(|(_, span, _)| *span);
+    for (start, span, content) in contents {
+        let range = start..start + span;
+        let autos: Vec<usize> = range
+            .clone()
+            .filter(|&i| is_auto(tracks[i], available))
+            .collect();
+        // Spanned fractions take whatever else is missing later on
+        let spans_fraction = available.is_some()
+            && tracks[range.clone()]
+                .iter()
+                .any(|it| matches!(it, Track::Fraction(_)));
+        if autos.is_empty() || spans_fraction {
+            continue;
+        }
+        let current: Scalar = sizes[range].iter().sum::<Scalar>() + gap * (span - 1) as Scalar;
+        let missing = content - current;
+        if missing > 0.0 {
+            for i in &autos {
+                sizes[*i] += missing / autos.len() as Scalar;
+            }
+        }
+    }
+
+    if let Some(available) = available {
+        let gaps = gap * tracks.len().saturating_sub(1) as Scalar;
+        let used: Scalar = sizes.iter().sum::<Scalar>() + gaps;
+        let free = (available - used).max(0.0);
+        let total: Scalar = tracks
+            .iter()
+            .map(|track| match track {
+                Track::Fraction(fraction) => *fraction,
+                _ => 0.0,
+            })
+            .sum();
+        if total > 0.0 {
+            for (size, track) in sizes.iter_mut().zip(tracks) {
+                if let Track::Fraction(fraction) = track {
+                    *size = free * fraction / total;
+                }
+            }
+        }
+    }
+
+    sizes
+}
+
+/// The start of every track and the total size of all tracks.
+fn track_offsets(sizes: &[Scalar], gap: Scalar) -> (Vec<Scalar>, Scalar) {
+    let mut offsets = Vec::with_capacity(sizes.len());
+    let mut offset = 0.0;
+    for size in sizes {
+        offsets.push(offset);
+        offset += size + gap;
+    }
+    let total = if sizes.is_empty() { 0.0 } else { offset - gap };
+    (offsets, total)
+}
+
+fn span_size(sizes: &[Scalar], gap: Scalar, start: usize, span: usize) -> Scalar {
+    sizes[start..start + span].iter().sum::<Scalar>() + gap * (span - 1) as Scalar
+}
+
+pub type State = Grid;
+
+impl Component for Grid {
+    type State = State;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> State {
+        props.clone()
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if *props != *state {
+            *state = props.clone();
+        }
+    }
+
+    fn layout(
+        state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        if state.columns.is_empty() {
+            if !children.is_empty() {
+                log::error!(
+                    "`Grid` layout {} has children but no columns",
+                    ui.full_debug_name()
+                );
+            }
+            return constraints.check_size(Size::zero());
+        }
+
+        let cells = place(children, state.columns.len(), ui);
+        let row_count = cells.iter().map(|it| it.row + it.row_span).max();
+        let mut rows = state.rows.clone();
+        if let Some(row_count) = row_count {
+            if row_count > rows.len() {
+                rows.resize(row_count, Track::Auto);
+            }
+        }
+
+        // Columns are measured with unbounded width
+        let max_width = constraints.max_width;
+        let column_contents: Vec<_> = cells
+            .iter()
+            .filter(|cell| {
+                let range = cell.column..cell.column + cell.column_span;
+                state.columns[range]
+                    .iter()
+                    .any(|&it| is_auto(it, max_width))
+            })
+            .map(|cell| {
+                let measure = BoxConstraints {
+                    max_height: constraints.max_height,
+                    ..BoxConstraints::default()
+                };
+                let size = ui.size(cell.child, measure);
+                (cell.column, cell.column_span, size.width)
+            })
+            .collect();
+        let columns = track_sizes(
+            &state.columns,
+            state.column_gap,
+            max_width,
+            &column_contents,
+        );
+
+        // Rows are measured with the width of the columns they are in
+        let max_height = constraints.max_height;
+        let row_contents: Vec<_> = cells
+            .iter()
+            .filter(|cell| {
+                let range = cell.row..cell.row + cell.row_span;
+                rows[range].iter().any(|&it| is_auto(it, max_height))
+            })
+            .map(|cell| {
+                let width = span_size(&columns, state.column_gap, cell.column, cell.column_span);
+                let measure = BoxConstraints::default().max_width(width);
+                let size = ui.size(cell.child, measure);
+                (cell.row, cell.row_span, size.height)
+            })
+            .collect();
+        let rows = track_sizes(&rows, state.row_gap, max_height, &row_contents);
+
+        let (column_offsets, width) = track_offsets(&columns, state.column_gap);
+        let (row_offsets, height) = track_offsets(&rows, state.row_gap);
+
+        for cell in &cells {
+            let size = Size::new(
+                span_size(&columns, state.column_gap, cell.column, cell.column_span),
+                span_size(&rows, state.row_gap, cell.row, cell.row_span),
+            );
+            ui.size(cell.child, BoxConstraints::new_tight(size));
+            let position = Position::new(column_offsets[cell.column], row_offsets[cell.row]);
+            ui.position(cell.child, position);
+        }
+
+        constraints.check_size(Size::new(width, height))
+    }
+}
+
+impl Component for GridItem {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props != state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        layout_single_child("GridItem", children, constraints, ui)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_track_sizes() {
+        let tracks = [
+            Track::Fixed(50.0),
+            Track::Auto,
+            Track::Fraction(1.0),
+            Track::Fraction(3.0),
+        ];
+        let contents = [(1, 1, 20.0), (0, 2, 90.0), (1, 2, 500.0)];
+        let sizes = track_sizes(&tracks, 10.0, Some(350.0), &contents);
+        // The first spanning child needs 10 more than the fixed track, gap and single child provide
+        assert_eq!(sizes, vec![50.0, 30.0, 60.0, 180.0]);
+
+        // Without a bound the fractions are auto tracks as well
+        let sizes = track_sizes(&tracks, 10.0, None, &contents);
+        assert_eq!(sizes, vec![50.0, 260.0, 230.0, 0.0]);
+    }
+}
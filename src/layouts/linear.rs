@@ -1,3 +1,4 @@
+use crate::layouts::flexible;
 use crate::*;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -6,10 +7,47 @@ pub enum Direction {
     Vertical,
 }
 
+/// How a `Linear` distributes its children along its direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainAxisAlignment {
+    /// Packs the children at the start.
+    Start,
+    /// Packs the children at the end.
+    End,
+    /// Packs the children in the middle.
+    Center,
+    /// Puts all free space between the children.
+    SpaceBetween,
+    /// Puts half as much free space before the first and after the last child
+    /// as between the children.
+    SpaceAround,
+    /// Puts the same amount of free space before, between and after the children.
+    SpaceEvenly,
+}
+
+/// How a `Linear` places its children across its direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossAxisAlignment {
+    /// Passes the cross axis constraints on to the children unchanged.
+    ///
+    /// All other alignments allow the children to be thinner than the `Linear`.
+    Start,
+    End,
+    Center,
+    /// Makes the children exactly as thick as the `Linear`.
+    Stretch,
+    /// Lines up the baselines of the children, see `UiLayout::baseline`.
+    ///
+    /// Vertical layouts and children without a baseline are placed at the start.
+    Baseline,
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub struct Linear {
     direction: Direction,
     spacing: Scalar,
+    main_alignment: MainAxisAlignment,
+    cross_alignment: CrossAxisAlignment,
 }
 
 impl Default for Linear {
@@ -17,6 +55,8 @@ impl Default for Linear {
         Linear {
             direction: Direction::Horizontal,
             spacing: 0.0,
+            main_alignment: MainAxisAlignment::Start,
+            cross_alignment: CrossAxisAlignment::Start,
         }
     }
 }
@@ -36,6 +76,16 @@ impl Linear {
         self.spacing = spacing;
         self
     }
+
+    pub fn main_axis_alignment(mut self, alignment: MainAxisAlignment) -> Self {
+        self.main_alignment = alignment;
+        self
+    }
+
+    pub fn cross_axis_alignment(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.cross_alignment = alignment;
+        self
+    }
 }
 
 pub type State = Linear;
@@ -63,82 +113,189 @@ impl Component for Linear {
         constraints: BoxConstraints,
         ui: &mut UiLayout,
     ) -> Size {
-        use self::Direction::*;
-        let is_limited = match state.direction {
-            Horizontal => constraints.max_width.is_some(),
-            Vertical => constraints.max_height.is_some(),
+        let direction = state.direction;
+        let max_length = direction.max_main(constraints);
+        let max_thickness = direction.max_cross(constraints);
+        let constraints = match (state.cross_alignment, max_thickness) {
+            (CrossAxisAlignment::Start, _) => constraints,
+            (CrossAxisAlignment::Stretch, Some(max)) => {
+                direction.with_cross(constraints, max, Some(max))
+            }
+            _ => direction.with_cross(constraints, 0.0, max_thickness),
         };
+        let constraints = direction.with_main(constraints, 0.0, max_length);
+        let spacing = state.spacing * children.len().saturating_sub(1) as Scalar;
 
-        let constraints = match state.direction {
-            Horizontal => constraints.min_width(0.0),
-            Vertical => constraints.min_height(0.0),
-        };
+        // Inflexible children are sized first, in order
+        let mut used = spacing;
+        let mut total_flex = 0.0;
+        for child in children.iter().cloned() {
+            if let Some((flex, _)) = flexible::flex_of(ui, child).filter(|_| max_length.is_some()) {
+                total_flex += flex;
+                continue;
+            }
+            let remaining = max_length.map(|max| (max - used).max(0.0));
+            let size = ui.size(child, direction.with_main(constraints, 0.0, remaining));
+            used += direction.main(size);
+        }
 
-        let mut length = -state.spacing;
-        let mut thickness = 0.0;
-        if !is_limited {
-            for child in children {
-                length += state.spacing;
-                let size = ui.size(*child, constraints);
-                let position = match state.direction {
-                    Horizontal => Position::new(length, 0.0),
-                    Vertical => Position::new(0.0, length),
-                };
-                ui.position(*child, position);
-                let (distance, thickness2) = match state.direction {
-                    Horizontal => (size.width, size.height),
-                    Vertical => (size.height, size.width),
-                };
-                length += distance;
-                if thickness2 > thickness {
-                    thickness = thickness2;
+        // The flexible ones share whatever is left
+        if let Some(max_length) = max_length {
+            let free = (max_length - used).max(0.0);
+            for child in children.iter().cloned() {
+                if let Some((flex, fit)) = flexible::flex_of(ui, child) {
+                    let share = if total_flex > 0.0 {
+                        free * flex / total_flex
+                    } else {
+                        0.0
+                    };
+                    let min = match fit {
+                        FlexFit::Tight => share,
+                        FlexFit::Loose => 0.0,
+                    };
+                    let size = ui.size(child, direction.with_main(constraints, min, Some(share)));
+                    used += direction.main(size);
                 }
             }
-        } else {
-            let max_length = match state.direction {
-                Horizontal => constraints.max_width.unwrap(),
-                Vertical => constraints.max_height.unwrap(),
+
+            if used > max_length {
+                log::warn!(
+                    "`Linear` layout {} has more children than it can fit",
+                    ui.full_debug_name()
+                );
+            }
+        }
+
+        // Free space is only distributed if there is a bound to fill
+        let length = match max_length {
+            Some(max) if state.main_alignment != MainAxisAlignment::Start => max.max(used),
+            _ => used,
+        };
+        let free = length - used;
+        let count = children.len() as Scalar;
+        let (leading, between) = match state.main_alignment {
+            MainAxisAlignment::Start => (0.0, 0.0),
+            MainAxisAlignment::End => (free, 0.0),
+            MainAxisAlignment::Center => (free / 2.0, 0.0),
+            MainAxisAlignment::SpaceBetween if children.len() > 1 => (0.0, free / (count - 1.0)),
+            MainAxisAlignment::SpaceBetween => (0.0, 0.0),
+            MainAxisAlignment::SpaceAround => (free / count / 2.0, free / count),
+            MainAxisAlignment::SpaceEvenly => (free / (count + 1.0), free / (count + 1.0)),
+        };
+
+        let baselines: Vec<Option<Scalar>> = children
+            .iter()
+            .map(|&child| match (state.cross_alignment, direction) {
+                (CrossAxisAlignment::Baseline, Direction::Horizontal) => ui.baseline(child),
+                _ => None,
+            })
+            .collect();
+        let max_baseline = baselines.iter().filter_map(|it| *it).fold(0.0, Scalar::max);
+
+        let mut thickness = direction.min_cross(constraints);
+        for (child, baseline) in children.iter().cloned().zip(&baselines) {
+            let cross = direction.cross(ui.get_size(child));
+            let offset = baseline.map(|it| max_baseline - it).unwrap_or(0.0);
+            thickness = thickness.max(offset + cross);
+        }
+
+        let mut main = leading;
+        for (child, baseline) in children.iter().cloned().zip(&baselines) {
+            let size = ui.get_size(child);
+            let free_cross = thickness - direction.cross(size);
+            let cross = match state.cross_alignment {
+                CrossAxisAlignment::Start | CrossAxisAlignment::Stretch => 0.0,
+                CrossAxisAlignment::End => free_cross,
+                CrossAxisAlignment::Center => free_cross / 2.0,
+                CrossAxisAlignment::Baseline => baseline.map(|it| max_baseline - it).unwrap_or(0.0),
             };
+            ui.position(child, direction.position(main, cross));
+            main += direction.main(size) + state.spacing + between;
+        }
 
-            for child in children {
-                length += state.spacing;
-                let constraints = match state.direction {
-                    Horizontal => constraints.max_width(max_length - length),
-                    Vertical => constraints.max_height(max_length - length),
-                };
-                let size = ui.size(*child, constraints);
-
-                let position = match state.direction {
-                    Horizontal => Position::new(length, 0.0),
-                    Vertical => Position::new(0.0, length),
-                };
-                ui.position(*child, position);
-
-                let (distance, thickness2) = match state.direction {
-                    Horizontal => (size.width, size.height),
-                    Vertical => (size.height, size.width),
-                };
-                length += distance;
-
-                if thickness2 > thickness {
-                    thickness = thickness2;
-                }
+        direction.size(length, thickness)
+    }
+}
 
-                if length >= max_length {
-                    if length > max_length || child != children.last().unwrap() {
-                        log::warn!(
-                            "`Linear` layout {} has more children than it can fit",
-                            ui.full_debug_name()
-                        );
-                    }
-                    break;
-                }
-            }
+impl Direction {
+    fn main(self, size: Size) -> Scalar {
+        match self {
+            Direction::Horizontal => size.width,
+            Direction::Vertical => size.height,
+        }
+    }
+
+    fn cross(self, size: Size) -> Scalar {
+        match self {
+            Direction::Horizontal => size.height,
+            Direction::Vertical => size.width,
+        }
+    }
+
+    fn size(self, main: Scalar, cross: Scalar) -> Size {
+        match self {
+            Direction::Horizontal => Size::new(main, cross),
+            Direction::Vertical => Size::new(cross, main),
+        }
+    }
+
+    fn position(self, main: Scalar, cross: Scalar) -> Position {
+        match self {
+            Direction::Horizontal => Position::new(main, cross),
+            Direction::Vertical => Position::new(cross, main),
+        }
+    }
+
+    fn max_main(self, constraints: BoxConstraints) -> Option<Scalar> {
+        match self {
+            Direction::Horizontal => constraints.max_width,
+            Direction::Vertical => constraints.max_height,
         }
+    }
+
+    fn max_cross(self, constraints: BoxConstraints) -> Option<Scalar> {
+        match self {
+            Direction::Horizontal => constraints.max_height,
+            Direction::Vertical => constraints.max_width,
+        }
+    }
+
+    fn min_cross(self, constraints: BoxConstraints) -> Scalar {
+        match self {
+            Direction::Horizontal => constraints.min_height,
+            Direction::Vertical => constraints.min_width,
+        }
+    }
 
-        match state.direction {
-            Horizontal => Size::new(length, thickness),
-            Vertical => Size::new(thickness, length),
+    fn with_cross(
+        self,
+        constraints: BoxConstraints,
+        min: Scalar,
+        max: Option<Scalar>,
+    ) -> BoxConstraints {
+        match self {
+            Direction::Horizontal => Direction::Vertical.with_main(constraints, min, max),
+            Direction::Vertical => Direction::Horizontal.with_main(constraints, min, max),
+        }
+    }
+
+    fn with_main(
+        self,
+        constraints: BoxConstraints,
+        min: Scalar,
+        max: Option<Scalar>,
+    ) -> BoxConstraints {
+        let mut constraints = constraints;
+        match self {
+            Direction::Horizontal => {
+                constraints.min_width = min;
+                constraints.max_width = max;
+            }
+            Direction::Vertical => {
+                constraints.min_height = min;
+                constraints.max_height = max;
+            }
         }
+        constraints
     }
 }
@@ -1,9 +1,9 @@
 use crate::*;
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq)]
 pub struct Offset {
-    x: Scalar,
-    y: Scalar,
+    pub(crate) x: Scalar,
+    pub(crate) y: Scalar,
 }
 
 impl Offset {
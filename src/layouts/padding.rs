@@ -1,11 +1,11 @@
 use crate::*;
 
-#[derive(Default, Clone, Copy, Debug)]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
 pub struct Padding {
-    top: Scalar,
-    right: Scalar,
-    bottom: Scalar,
-    left: Scalar,
+    pub(crate) top: Scalar,
+    pub(crate) right: Scalar,
+    pub(crate) bottom: Scalar,
+    pub(crate) left: Scalar,
 }
 
 impl Padding {
@@ -1,36 +1,267 @@
+use crate::layouts::layout_single_child;
 use crate::*;
 
-#[derive(Default)]
-pub struct Stack;
+/// How a `Stack` sizes its children that are not `Positioned`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackFit {
+    /// The children may be as small as they want,
+    /// the `Stack` is as large as the largest of them.
+    Loose,
+    /// The children and the `Stack` are as large as the constraints allow.
+    Expand,
+}
+
+/// Lays out all children on top of each other.
+///
+/// Children are aligned within the `Stack` by `alignment`, unless they are wrapped
+/// in an `Aligned` or `Positioned` component. `Positioned` children do not affect
+/// the size of the `Stack`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Stack {
+    alignment: Alignment,
+    fit: StackFit,
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Stack {
+            alignment: Alignment::CENTER,
+            fit: StackFit::Loose,
+        }
+    }
+}
+
+impl Stack {
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn fit(mut self, fit: StackFit) -> Self {
+        self.fit = fit;
+        self
+    }
+}
+
+/// Overrides the alignment of its child within a `Stack`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Aligned {
+    pub(crate) alignment: Alignment,
+}
+
+impl Aligned {
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+/// Places its child at a fixed distance from the edges of a `Stack`.
+///
+/// If both opposing edges are set, the child is stretched in between them.
+/// Otherwise it is given `width` or `height` as tight size if these are set.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Positioned {
+    pub(crate) top: Option<Scalar>,
+    pub(crate) right: Option<Scalar>,
+    pub(crate) bottom: Option<Scalar>,
+    pub(crate) left: Option<Scalar>,
+    pub(crate) width: Option<Scalar>,
+    pub(crate) height: Option<Scalar>,
+}
+
+impl Positioned {
+    /// Stretches the child over the whole `Stack`.
+    pub fn fill(self) -> Self {
+        self.top(0.0).right(0.0).bottom(0.0).left(0.0)
+    }
+
+    pub fn top(mut self, top: Scalar) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    pub fn right(mut self, right: Scalar) -> Self {
+        self.right = Some(right);
+        self
+    }
+
+    pub fn bottom(mut self, bottom: Scalar) -> Self {
+        self.bottom = Some(bottom);
+        self
+    }
+
+    pub fn left(mut self, left: Scalar) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    pub fn width(mut self, width: Scalar) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn height(mut self, height: Scalar) -> Self {
+        self.height = Some(height);
+        self
+    }
+}
+
+/// The extent and position of a `Positioned` child along one axis.
+fn place(
+    start: Option<Scalar>,
+    end: Option<Scalar>,
+    extent: Option<Scalar>,
+    available: Scalar,
+) -> (Option<Scalar>, Option<Scalar>) {
+    match (start, end) {
+        (Some(start), Some(end)) => (Some((available - start - end).max(0.0)), Some(start)),
+        _ => (extent, start),
+    }
+}
+
+pub type State = Stack;
+pub type Msg = ();
+pub type Event = ();
 
 impl Component for Stack {
-    type State = ();
-    type Msg = ();
-    type Event = ();
+    type State = State;
+    type Msg = Msg;
+    type Event = Event;
 
-    fn init(_props: &Self) -> Self::State {}
+    fn init(props: &Self) -> State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if *props != *state {
+            *state = *props;
+        }
+    }
 
     fn layout(
-        _state: &Self::State,
+        state: &Self::State,
         children: &[Cid],
         constraints: BoxConstraints,
         ui: &mut UiLayout,
     ) -> Size {
-        let constraints = constraints.min(Size::zero());
-        let mut largest = Size::zero();
+        let loose = constraints.min(Size::zero());
+        let child_constraints = match (state.fit, constraints.max_width, constraints.max_height) {
+            (StackFit::Expand, Some(width), Some(height)) => {
+                BoxConstraints::new_tight(Size::new(width, height))
+            }
+            _ => loose,
+        };
 
+        let mut largest = Size::zero();
         for child in children.iter().cloned() {
-            let size = ui.size(child, constraints);
-            largest = largest.max(size);
+            if ui.state::<Positioned>(child).is_none() {
+                let size = ui.size(child, child_constraints);
+                largest = largest.max(size);
+            }
         }
+        let size = constraints.check_size(largest);
 
         for child in children.iter().cloned() {
-            let size = ui.get_size(child);
-            let x = (largest.width - size.width) / 2.0;
-            let y = (largest.height - size.height) / 2.0;
-            ui.position(child, Position::new(x, y));
+            if let Some(positioned) = ui.state::<Positioned>(child).copied() {
+                let (width, left) = place(
+                    positioned.left,
+                    positioned.right,
+                    positioned.width,
+                    size.width,
+                );
+                let (height, top) = place(
+                    positioned.top,
+                    positioned.bottom,
+                    positioned.height,
+                    size.height,
+                );
+
+                let mut constraints = BoxConstraints::default().max(size);
+                if let Some(width) = width {
+                    constraints = constraints.min_width(width).max_width(width);
+                }
+                if let Some(height) = height {
+                    constraints = constraints.min_height(height).max_height(height);
+                }
+                let child_size = ui.size(child, constraints);
+
+                let aligned = state.alignment.within(size, child_size);
+                let x = left
+                    .or_else(|| {
+                        positioned
+                            .right
+                            .map(|it| size.width - it - child_size.width)
+                    })
+                    .unwrap_or(aligned.x);
+                let y = top
+                    .or_else(|| {
+                        positioned
+                            .bottom
+                            .map(|it| size.height - it - child_size.height)
+                    })
+                    .unwrap_or(aligned.y);
+                ui.position(child, Position::new(x, y));
+            } else {
+                let alignment = ui
+                    .state::<Aligned>(child)
+                    .map(|it| it.alignment)
+                    .unwrap_or(state.alignment);
+                let child_size = ui.get_size(child);
+                ui.position(child, alignment.within(size, child_size));
+            }
         }
 
-        largest
+        size
+    }
+}
+
+impl Component for Aligned {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props != state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        layout_single_child("Aligned", children, constraints, ui)
+    }
+}
+
+impl Component for Positioned {
+    type State = Self;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        *props
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props != state {
+            *state = *props;
+        }
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        layout_single_child("Positioned", children, constraints, ui)
     }
 }
@@ -1,5 +1,8 @@
 pub type Scalar = f32;
 
+pub mod animation;
+pub use self::animation::*;
+
 mod bounds;
 pub use self::bounds::{Bounds, Position, Size};
 
@@ -14,12 +17,17 @@ mod constraints;
 pub use self::constraints::BoxConstraints;
 
 mod ids;
-use self::ids::IidSecret;
+use self::ids::IidKey;
 pub use self::ids::{Cid, Iid};
 
 pub mod ui;
-use self::ui::{find_focus_state, full_debug_name_of, TypeIds, UiData, UiInputBase, UiRender};
-pub use self::ui::{FocusState, UiDerive, UiInput, UiLayout, UiUpdate, UiView};
+use self::ui::{
+    find_ancestor, find_context, find_focus_state, frame_request, full_debug_name_of, is_alive,
+    FrameRequest, Frames, Tasks, Timers, TypeIds, UiData, UiInputBase, UiRender, Viewing,
+};
+pub use self::ui::{
+    FocusState, Node, Query, TimerHandle, UiDerive, UiInput, UiLayout, UiUpdate, UiView,
+};
 
 mod mutable;
 pub use self::mutable::Mut;
@@ -36,6 +44,9 @@ pub use self::widgets::*;
 mod color;
 pub use self::color::Color;
 
+mod bitmap;
+pub use self::bitmap::Bitmap;
+
 mod input;
 use self::input::Input;
 pub use self::input::{
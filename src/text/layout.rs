@@ -1,8 +1,10 @@
-use crate::{Bounds, Position, Size};
+use crate::{Bounds, Position, Scalar, Size};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextLayout {
     pub size: Size,
+    /// The distance from the top to the baseline of the text
+    pub baseline: Scalar,
     pub glyphs: Vec<LayoutGlyph>,
 }
 
@@ -10,6 +12,7 @@ impl Default for TextLayout {
     fn default() -> Self {
         TextLayout {
             size: Size::zero(),
+            baseline: 0.0,
             glyphs: Vec::default(),
         }
     }
@@ -1,6 +1,28 @@
 mod data;
 pub use self::data::FocusState;
-pub(crate) use self::data::{find_focus_state, full_debug_name_of, TypeIds, UiData};
+pub(crate) use self::data::{
+    find_ancestor, find_context, find_focus_state, full_debug_name_of, is_alive, TypeIds, UiData,
+    Viewing,
+};
+
+/// The `UiData::messages` of a single component whose `Msg` is `u32`.
+#[cfg(test)]
+pub(crate) fn test_messages() -> Vec<Option<Box<dyn std::any::Any>>> {
+    vec![Some(Box::<Vec<u32>>::default())]
+}
+
+mod tasks;
+pub(crate) use self::tasks::Tasks;
+
+mod frames;
+pub(crate) use self::frames::{frame_request, FrameRequest, Frames};
+
+mod timers;
+pub use self::timers::TimerHandle;
+pub(crate) use self::timers::Timers;
+
+mod query;
+pub use self::query::{Node, Query};
 
 mod view;
 pub use self::view::UiView;
@@ -1,7 +1,8 @@
-use crate::{Cid, Component, ComponentPointer, IidSecret, Position, Size};
+use crate::{Cid, Component, ComponentPointer, Frames, IidKey, Position, Size, Tasks, Timers};
 use fnv::FnvHashMap;
 use smallvec::SmallVec;
 use std::any::{Any, TypeId};
+use std::time::Instant;
 
 /// Contains all data that is necessary for the ui
 #[derive(Default)]
@@ -19,8 +20,8 @@ pub(crate) struct UiData {
     /// is being used to render and layout the comps like a graph
     pub(crate) children: Vec<Vec<Cid>>,
     /// Similar to `UiData::children` but maps the per-component
-    /// child identifier to the associated `Cid` used to index the `UiData`.
-    pub(crate) creations: Vec<FnvHashMap<IidSecret, Cid>>,
+    /// child identifier (including its key) and type to the associated `Cid` used to index the `UiData`.
+    pub(crate) creations: Vec<FnvHashMap<(IidKey, TypeIds), Cid>>,
     /// Stores a `Component`s `Position` relative to its parent
     pub(crate) position: Vec<Position>,
     /// Stores the `Component`s final `Size` that fits its `BoxConstraints`
@@ -37,12 +38,28 @@ pub(crate) struct UiData {
     /// Cache events. Similar to `messages` this is a `Vec<Vec<Component::Event>>`
     pub(crate) events: Vec<Box<Any>>,
 
+    /// Marks the `Component`s that have been `set` during the current `UiView` pass.
+    /// Everything that is left unmarked afterwards gets removed by `UiData::sweep`.
+    pub(crate) visited: Vec<bool>,
+    /// The amount of children that have been attached to a `Component` during
+    /// the current `UiView` pass. The children are kept in the order they have been `set`.
+    attached: Vec<usize>,
+
     /// The focused component, whether or not this is being honored depends on
     /// the specific component.
     pub(crate) focused: Option<Cid>,
 
-    /// The next `Cid` that will be allocated when needed
-    id_count: usize,
+    /// The generation of every slot, it is increased whenever a slot is freed
+    pub(crate) generations: Vec<u32>,
+    /// Slots that have been freed and can be reused by `UiData::fresh_id`
+    free: Vec<usize>,
+
+    /// The futures that have been spawned by the `Component`s
+    pub(crate) tasks: Tasks,
+    /// The timers that have been started by the `Component`s
+    pub(crate) timers: Timers,
+    /// The frame clock and the pending frame requests of the `Component`s
+    pub(crate) frames: Frames,
 }
 
 /// The focus state of a component.
@@ -56,6 +73,63 @@ pub enum FocusState {
     Contains,
 }
 
+/// The states of the components whose `view` function is currently being run,
+/// as they have been moved out of `UiData::state` during that time.
+pub(crate) struct Viewing<'a> {
+    pub(crate) cid: Cid,
+    pub(crate) state: &'a dyn Any,
+    pub(crate) outer: Option<&'a Viewing<'a>>,
+}
+
+impl<'a> Viewing<'a> {
+    pub(crate) fn find(&self, cid: Cid) -> Option<&'a dyn Any> {
+        if self.cid == cid {
+            Some(self.state)
+        } else {
+            self.outer.and_then(|outer| outer.find(cid))
+        }
+    }
+}
+
+/// Finds the closest ancestor of `of` whose type matches `target`.
+pub(crate) fn find_ancestor(
+    parent: &[Option<Cid>],
+    typeids: &[TypeIds],
+    of: Cid,
+    target: TypeIds,
+) -> Option<Cid> {
+    let mut current = parent[of.get()];
+    while let Some(ancestor) = current {
+        if typeids[ancestor.get()] == target {
+            return Some(ancestor);
+        }
+        current = parent[ancestor.get()];
+    }
+    None
+}
+
+/// Looks up the state of the closest ancestor of `of` which is a `Provider`.
+///
+/// The state of a `Component` whose `view` is running is found through `viewing`.
+pub(crate) fn find_context<'s, Provider: Component>(
+    parent: &[Option<Cid>],
+    typeids: &[TypeIds],
+    state: &'s [Option<Box<dyn Any>>],
+    of: Cid,
+    viewing: Option<&'s Viewing<'s>>,
+) -> Option<&'s Provider::State> {
+    let provider = find_ancestor(parent, typeids, of, TypeIds::of::<Provider>())?;
+    let state = match state[provider.get()] {
+        Some(ref state) => &**state,
+        None => viewing?.find(provider)?,
+    };
+    state.downcast_ref()
+}
+
+pub(crate) fn is_alive(generations: &[u32], id: Cid) -> bool {
+    generations.get(id.get()) == Some(&id.generation())
+}
+
 pub(crate) fn find_focus_state(of: Cid, focused: Cid, children: &Vec<Vec<Cid>>) -> FocusState {
     use FocusState::*;
     let cid = of;
@@ -76,10 +150,17 @@ pub(crate) fn find_focus_state(of: Cid, focused: Cid, children: &Vec<Vec<Cid>>)
 
 impl UiData {
     pub(crate) fn fresh_id(&mut self) -> Cid {
-        let id = Cid::new(self.id_count);
+        if let Some(index) = self.free.pop() {
+            // The slot has already been reset by `UiData::unmount`
+            let id = Cid::new(index, self.generations[index]);
+            log::trace!("Reused id {:?}", id);
+            return id;
+        }
+
+        let id = Cid::new(self.typeids.len(), 0);
         log::trace!("Allocated new id {:?}", id);
-        self.id_count += 1;
 
+        self.generations.push(0);
         self.typeids.push(TypeIds::void());
         self.name.push("");
         self.pointer.push(ComponentPointer::default());
@@ -89,14 +170,196 @@ impl UiData {
         self.position.push(Position::zero());
         self.size.push(Size::zero());
         self.state.push(None);
-        self.messages.push(Some(Box::new(Vec::<()>::new())));
-        self.events.push(Box::new(Vec::<()>::new()));
+        self.messages.push(Some(Box::<Vec<()>>::default()));
+        self.events.push(Box::<Vec<()>>::default());
+        self.visited.push(false);
+        self.attached.push(0);
 
         id
     }
 
+    /// Unmarks all `Component`s, this has to be called before a `UiView` pass.
+    pub(crate) fn begin_view_pass(&mut self) {
+        for visited in &mut self.visited {
+            *visited = false;
+        }
+        for attached in &mut self.attached {
+            *attached = 0;
+        }
+    }
+
+    /// Marks `cid` as visited and moves it behind the children of
+    /// its parent that have already been attached during this pass.
+    pub(crate) fn attach(&mut self, cid: Cid) {
+        if self.visited[cid.get()] {
+            log::warn!(
+                "The component {} has been set multiple times during one view pass",
+                self.full_debug_name_of(cid)
+            );
+            return;
+        }
+        self.visited[cid.get()] = true;
+
+        if let Some(parent) = self.parent[cid.get()] {
+            let position = self.attached[parent.get()];
+            let children = &mut self.children[parent.get()];
+            if children.get(position) != Some(&cid) {
+                if let Some(current) = children.iter().position(|child| *child == cid) {
+                    children.remove(current);
+                }
+                children.insert(position, cid);
+            }
+            self.attached[parent.get()] += 1;
+        }
+    }
+
+    /// Removes all `Component`s below `root` that have not been visited
+    /// during the last `UiView` pass, together with their whole subtree.
+    pub(crate) fn sweep(&mut self, root: Cid) {
+        let mut unvisited = Vec::new();
+        self.find_unvisited(root, &mut unvisited);
+        if unvisited.is_empty() {
+            return;
+        }
+
+        for cid in unvisited {
+            self.remove_subtree(cid);
+        }
+        self.forget_removed();
+    }
+
+    fn find_unvisited(&self, cid: Cid, unvisited: &mut Vec<Cid>) {
+        for child in &self.children[cid.get()] {
+            if self.visited[child.get()] {
+                self.find_unvisited(*child, unvisited);
+            } else {
+                unvisited.push(*child);
+            }
+        }
+    }
+
+    /// Removes the unvisited `cid` and everything below it.
+    fn remove_subtree(&mut self, cid: Cid) {
+        if let Some(parent) = self.parent[cid.get()] {
+            self.children[parent.get()].retain(|child| *child != cid);
+        }
+
+        let mut removed = Vec::new();
+        self.collect_subtree(cid, &mut removed);
+        for cid in removed {
+            self.unmount(cid);
+        }
+    }
+
+    fn collect_subtree(&self, cid: Cid, collected: &mut Vec<Cid>) {
+        collected.push(cid);
+        for child in &self.children[cid.get()] {
+            self.collect_subtree(*child, collected);
+        }
+    }
+
+    /// The creator of a `Component` is not necessarily its parent,
+    /// so every remaining `Component` has to forget about the removed ones.
+    fn forget_removed(&mut self) {
+        let generations = &self.generations;
+        for creations in &mut self.creations {
+            creations.retain(|_, cid| is_alive(generations, *cid));
+        }
+    }
+
+    /// Drops the state of `cid` and resets its slot.
+    fn unmount(&mut self, cid: Cid) {
+        log::trace!("Unmounting component {}", self.full_debug_name_of(cid));
+
+        if self.focused == Some(cid) {
+            self.focused = None;
+        }
+        self.tasks.cancel(cid);
+        self.timers.cancel(cid);
+        self.frames.cancel(cid);
+
+        let id = cid.get();
+        self.typeids[id] = TypeIds::void();
+        self.name[id] = "";
+        self.pointer[id] = ComponentPointer::default();
+        self.parent[id] = None;
+        self.children[id].clear();
+        self.creations[id].clear();
+        self.position[id] = Position::zero();
+        self.size[id] = Size::zero();
+        self.state[id] = None;
+        self.messages[id] = Some(Box::<Vec<()>>::default());
+        self.events[id] = Box::<Vec<()>>::default();
+        self.visited[id] = false;
+        self.attached[id] = 0;
+
+        self.generations[id] = self.generations[id].wrapping_add(1);
+        self.free.push(id);
+    }
+
+    /// Checks whether `id` still refers to the component it has been created for.
+    pub(crate) fn is_alive(&self, id: Cid) -> bool {
+        is_alive(&self.generations, id)
+    }
+
+    /// Checks whether the component behind `id` has not been initialized yet.
+    /// Comparing the `TypeIds` would not suffice as `TypeIds::void` is a valid value.
     pub(crate) fn is_fresh(&self, id: Cid) -> bool {
-        self.typeids[id.get()] == TypeIds::void()
+        self.state[id.get()].is_none()
+    }
+
+    /// Polls the woken tasks, returns whether any of them delivered a message.
+    pub(crate) fn poll_tasks(&mut self) -> bool {
+        self.tasks.poll(&mut self.messages)
+    }
+
+    /// Delivers the messages of all due timers, returns whether there were any.
+    pub(crate) fn poll_timers(&mut self) -> bool {
+        self.timers.poll(Instant::now(), &mut self.messages)
+    }
+
+    /// Starts a new frame at `now`, returns whether any frame request has been delivered.
+    pub(crate) fn begin_frame(&mut self, now: Instant) -> bool {
+        self.frames.begin(now, &mut self.messages)
+    }
+
+    /// Queues `msg` for the next `UiUpdate` of `cid`.
+    pub(crate) fn push_message<Target: Component>(&mut self, cid: Cid, msg: Target::Msg) {
+        if !self.is_alive(cid) || self.is_fresh(cid) {
+            log::error!("Tried to send a message to the stale or fresh {:?}", cid);
+            return;
+        }
+        if self.typeids[cid.get()] != TypeIds::of::<Target>() {
+            log::error!("Tried to send a message of the wrong type to {:?}", cid);
+            return;
+        }
+        match self.messages[cid.get()].as_mut() {
+            Some(messages) => messages
+                .downcast_mut::<Vec<Target::Msg>>()
+                .unwrap()
+                .push(msg),
+            None => log::error!("Tried to send a message to {:?} during its update", cid),
+        }
+    }
+
+    /// Takes all events that have been emitted by `cid` so far.
+    pub(crate) fn take_events<E: 'static>(&mut self, cid: Cid) -> Vec<E> {
+        match self.events[cid.get()].downcast_mut::<Vec<E>>() {
+            Some(events) => std::mem::take(events),
+            None => {
+                log::error!("Tried to take events of the wrong type");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Looks up the state of the closest ancestor of `of` which is a `Provider`.
+    pub(crate) fn context<'s, Provider: Component>(
+        &'s self,
+        of: Cid,
+        viewing: Option<&'s Viewing<'s>>,
+    ) -> Option<&'s Provider::State> {
+        find_context::<Provider>(&self.parent, &self.typeids, &self.state, of, viewing)
     }
 
     pub(crate) fn full_debug_name_of(&self, id: Cid) -> String {
@@ -146,3 +409,137 @@ pub(crate) fn full_debug_name_of(
         .rev()
         .fold(String::new(), |acc, n| acc + "/" + n)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct Leaf;
+
+    impl Component for Leaf {
+        type State = ();
+        type Msg = u32;
+        type Event = ();
+
+        fn init(_props: &Self) -> Self::State {}
+    }
+
+    #[derive(Default)]
+    struct Provider;
+
+    impl Component for Provider {
+        type State = &'static str;
+        type Msg = ();
+        type Event = ();
+
+        fn init(_props: &Self) -> Self::State {
+            ""
+        }
+    }
+
+    /// Creates a `Comp` below `parent` like `UiView::add` would.
+    fn mount_as<Comp: Component>(
+        data: &mut UiData,
+        parent: Option<Cid>,
+        state: Comp::State,
+    ) -> Cid {
+        let cid = data.fresh_id();
+        data.typeids[cid.get()] = TypeIds::of::<Comp>();
+        data.parent[cid.get()] = parent;
+        data.state[cid.get()] = Some(Box::new(state));
+        data.messages[cid.get()] = Some(Box::<Vec<Comp::Msg>>::default());
+        data.attach(cid);
+        cid
+    }
+
+    fn mount(data: &mut UiData, parent: Option<Cid>) -> Cid {
+        mount_as::<Leaf>(data, parent, ())
+    }
+
+    /// Creates `root -> (a -> a1, b)` and runs a view pass that only sets `b`.
+    fn sweep_a(data: &mut UiData, before_sweep: impl FnOnce(&mut UiData, Cid)) -> [Cid; 4] {
+        data.begin_view_pass();
+        let root = mount(data, None);
+        let a = mount(data, Some(root));
+        let a1 = mount(data, Some(a));
+        let b = mount(data, Some(root));
+        before_sweep(data, a1);
+
+        data.begin_view_pass();
+        data.attach(b);
+        data.sweep(root);
+        [root, a, a1, b]
+    }
+
+    #[test]
+    fn test_sweep_removes_subtree() {
+        let mut data = UiData::default();
+        let [root, a, a1, b] = sweep_a(&mut data, |_, _| {});
+
+        assert!(!data.is_alive(a));
+        assert!(!data.is_alive(a1));
+        assert!(data.is_alive(b));
+        assert_eq!(data.children[root.get()], vec![b]);
+    }
+
+    #[test]
+    fn test_sweep_clears_focus() {
+        let mut data = UiData::default();
+        sweep_a(&mut data, |data, a1| data.focused = Some(a1));
+
+        assert_eq!(data.focused, None);
+    }
+
+    #[test]
+    fn test_sweep_cancels_pending_work() {
+        let mut data = UiData::default();
+        let [.., b] = sweep_a(&mut data, |data, a1| {
+            data.tasks.spawn(a1, async { 1u32 }, |it| it);
+            data.timers.after(a1, Duration::from_secs(1), 2u32);
+            data.frames.request(a1, 3u32);
+        });
+
+        assert!(!data.poll_tasks());
+        assert_eq!(data.timers.next_deadline(), None);
+        assert!(!data.frames.is_requested());
+        // `b` has not been affected
+        data.push_message::<Leaf>(b, 4);
+        let messages = data.messages[b.get()].as_ref().unwrap();
+        assert_eq!(messages.downcast_ref::<Vec<u32>>(), Some(&vec![4]));
+    }
+
+    #[test]
+    fn test_stale_cid_is_rejected() {
+        let mut data = UiData::default();
+        let root = mount(&mut data, None);
+        let old = mount(&mut data, Some(root));
+        data.begin_view_pass();
+        data.sweep(root);
+
+        let new = mount(&mut data, Some(root));
+        assert_eq!(new.get(), old.get());
+        assert!(new.generation() > old.generation());
+        assert!(!data.is_alive(old));
+        assert!(data.is_alive(new));
+
+        data.push_message::<Leaf>(old, 1);
+        let messages = data.messages[new.get()].as_ref().unwrap();
+        assert_eq!(messages.downcast_ref::<Vec<u32>>(), Some(&vec![]));
+    }
+
+    #[test]
+    fn test_context() {
+        let mut data = UiData::default();
+        let outer = mount_as::<Provider>(&mut data, None, "outer");
+        let inner = mount_as::<Provider>(&mut data, Some(outer), "inner");
+        let leaf = mount(&mut data, Some(inner));
+
+        assert_eq!(data.context::<Provider>(leaf, None), Some(&"inner"));
+        // A component is not its own provider
+        assert_eq!(data.context::<Provider>(inner, None), Some(&"outer"));
+        assert_eq!(data.context::<Provider>(outer, None), None);
+        assert_eq!(data.context::<Leaf>(leaf, None), None);
+    }
+}
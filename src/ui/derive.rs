@@ -1,15 +1,69 @@
-use crate::{webrender::FontManager, Font, FontSize, TextLayout};
+use crate::{
+    frame_request, Cid, Component, Font, FontManager, FontSize, FrameRequest, TextLayout, UiData,
+    Viewing,
+};
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
 
 pub struct UiDerive<'a> {
     fonts: &'a FontManager,
+    data: &'a UiData,
+    cid: Cid,
+    viewing: Option<&'a Viewing<'a>>,
+    frame_requests: RefCell<Vec<FrameRequest>>,
+    tick: Cell<bool>,
 }
 
 impl<'a> UiDerive<'a> {
-    pub fn new(fonts: &'a FontManager) -> Self {
-        UiDerive { fonts }
+    pub(crate) fn new(
+        fonts: &'a FontManager,
+        data: &'a UiData,
+        cid: Cid,
+        viewing: Option<&'a Viewing<'a>>,
+    ) -> Self {
+        UiDerive {
+            fonts,
+            data,
+            cid,
+            viewing,
+            frame_requests: RefCell::new(Vec::new()),
+            tick: Cell::new(false),
+        }
+    }
+
+    /// The frame requests and whether a tick has been requested.
+    pub(crate) fn into_frame_requests(self) -> (Vec<FrameRequest>, bool) {
+        (self.frame_requests.into_inner(), self.tick.get())
     }
 
     pub fn layout(&self, text: &str, font: Option<&Font>, size: FontSize) -> TextLayout {
         self.fonts.layout(text, font, size)
     }
+
+    /// Sends `msg` to this component at the beginning of the next frame.
+    pub fn request_frame<M: 'static>(&self, msg: M) {
+        if self.data.typeids[self.cid.get()].message == TypeId::of::<M>() {
+            self.frame_requests
+                .borrow_mut()
+                .push(frame_request(self.cid, msg));
+        } else {
+            log::error!("Tried to request a frame with the wrong message type");
+        }
+    }
+
+    /// Calls `Component::tick` of this component at the beginning of the next frame.
+    pub fn request_tick(&self) {
+        self.tick.set(true);
+    }
+
+    /// The time at which the current frame began.
+    pub fn frame_time(&self) -> Instant {
+        self.data.frames.time()
+    }
+
+    /// Returns the state of the closest ancestor which is a `Provider`.
+    pub fn context<Provider: Component>(&self) -> Option<&Provider::State> {
+        self.data.context::<Provider>(self.cid, self.viewing)
+    }
 }
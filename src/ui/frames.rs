@@ -0,0 +1,144 @@
+use crate::Cid;
+use std::any::Any;
+use std::time::Instant;
+
+/// Pushes a message into the `Vec<Component::Msg>` of its owner.
+type Delivery = Box<dyn FnOnce(&mut Box<dyn Any>)>;
+
+/// A message waiting to be delivered at the beginning of the next frame.
+pub(crate) type FrameRequest = (Cid, Delivery);
+
+pub(crate) fn frame_request<M: 'static>(owner: Cid, msg: M) -> FrameRequest {
+    let delivery = move |messages: &mut Box<dyn Any>| {
+        messages.downcast_mut::<Vec<M>>().unwrap().push(msg);
+    };
+    (owner, Box::new(delivery))
+}
+
+/// The frame clock and the components waiting for the next frame.
+pub(crate) struct Frames {
+    time: Instant,
+    requests: Vec<FrameRequest>,
+    /// The components to tick in the next frame
+    ticks: Vec<Cid>,
+    /// The components to tick in the current frame
+    due: Vec<Cid>,
+}
+
+impl Default for Frames {
+    fn default() -> Self {
+        Frames {
+            time: Instant::now(),
+            requests: Vec::new(),
+            ticks: Vec::new(),
+            due: Vec::new(),
+        }
+    }
+}
+
+impl Frames {
+    /// The time at which the current frame began.
+    pub(crate) fn time(&self) -> Instant {
+        self.time
+    }
+
+    /// Delivers `msg` to `owner` at the beginning of the next frame.
+    pub(crate) fn request<M: 'static>(&mut self, owner: Cid, msg: M) {
+        self.requests.push(frame_request(owner, msg));
+    }
+
+    /// Calls `Component::tick` of `owner` at the beginning of the next frame.
+    pub(crate) fn request_tick(&mut self, owner: Cid) {
+        if !self.ticks.contains(&owner) {
+            self.ticks.push(owner);
+        }
+    }
+
+    /// Whether `owner` has to be ticked in the current frame, which it is only once.
+    pub(crate) fn take_tick(&mut self, owner: Cid) -> bool {
+        match self.due.iter().position(|cid| *cid == owner) {
+            Some(index) => {
+                self.due.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn extend(&mut self, requests: impl IntoIterator<Item = FrameRequest>) {
+        self.requests.extend(requests);
+    }
+
+    /// Whether any component is waiting for the next frame.
+    pub(crate) fn is_requested(&self) -> bool {
+        !self.requests.is_empty() || !self.ticks.is_empty()
+    }
+
+    /// Drops all requests of `owner`.
+    pub(crate) fn cancel(&mut self, owner: Cid) {
+        self.requests.retain(|(cid, _)| *cid != owner);
+        self.ticks.retain(|cid| *cid != owner);
+        self.due.retain(|cid| *cid != owner);
+    }
+
+    /// Starts a new frame at `now` and delivers all requests made during the last one.
+    /// Returns whether any message has been delivered or any component is due to tick.
+    pub(crate) fn begin(&mut self, now: Instant, messages: &mut [Option<Box<dyn Any>>]) -> bool {
+        self.time = now;
+        self.due = std::mem::take(&mut self.ticks);
+        let mut delivered = !self.due.is_empty();
+        for (owner, delivery) in self.requests.drain(..) {
+            match messages[owner.get()].as_mut() {
+                Some(messages) => {
+                    delivery(messages);
+                    delivered = true;
+                }
+                None => log::error!("Tried to deliver a frame request during an update"),
+            }
+        }
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ui::test_messages;
+    use std::time::Duration;
+
+    #[test]
+    fn test_delivers_on_next_frame() {
+        let mut frames = Frames::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+        let now = Instant::now() + Duration::from_millis(16);
+
+        frames.request(owner, 1u32);
+        assert!(frames.is_requested());
+        assert!(frames.begin(now, &mut messages));
+        assert_eq!(frames.time(), now);
+
+        let delivered = messages[0].as_ref().unwrap().downcast_ref::<Vec<u32>>();
+        assert_eq!(delivered, Some(&vec![1]));
+        assert!(!frames.is_requested());
+        assert!(!frames.begin(now, &mut messages));
+    }
+
+    #[test]
+    fn test_ticks_once_per_request() {
+        let mut frames = Frames::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+
+        frames.request_tick(owner);
+        frames.request_tick(owner);
+        assert!(frames.is_requested());
+        assert!(!frames.take_tick(owner));
+
+        assert!(frames.begin(Instant::now(), &mut messages));
+        assert!(!frames.is_requested());
+        assert!(frames.take_tick(owner));
+        assert!(!frames.take_tick(owner));
+        assert!(!frames.begin(Instant::now(), &mut messages));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{BoxConstraints, Cid, ComponentPointer, Position, Size, UiData};
+use crate::{BoxConstraints, Cid, ComponentPointer, Position, Scalar, Size, UiData};
 use std::any::Any;
 
 pub struct UiLayout<'a> {
@@ -9,6 +9,7 @@ pub struct UiLayout<'a> {
     position: &'a mut Vec<Position>,
     size: &'a mut Vec<Size>,
     state: &'a Vec<Option<Box<Any>>>,
+    generations: &'a Vec<u32>,
     current: Cid,
 }
 
@@ -24,6 +25,7 @@ impl<'a> UiLayout<'a> {
             position: &mut data.position,
             size: &mut data.size,
             state: &mut data.state,
+            generations: &data.generations,
             current: Cid::invalid(),
         };
 
@@ -31,6 +33,9 @@ impl<'a> UiLayout<'a> {
     }
 
     pub fn size(&mut self, child: Cid, constraints: BoxConstraints) -> Size {
+        if !self.is_alive(child) {
+            return Size::zero();
+        }
         let layout = self.pointer[child.get()].layout;
 
         let state = self.state[child.get()].as_ref().unwrap();
@@ -48,14 +53,58 @@ impl<'a> UiLayout<'a> {
     }
 
     pub fn get_size(&self, child: Cid) -> Size {
+        if !self.is_alive(child) {
+            return Size::zero();
+        }
         self.size[child.get()]
     }
 
     pub fn position(&mut self, child: Cid, position: Position) {
+        if !self.is_alive(child) {
+            return;
+        }
         self.position[child.get()] = position;
     }
 
+    /// The distance from the top of the already sized `child` to its baseline.
+    ///
+    /// Components without a baseline of their own use the one of their first child.
+    pub fn baseline(&self, child: Cid) -> Option<Scalar> {
+        if !self.is_alive(child) {
+            return None;
+        }
+        let state = self.state[child.get()].as_ref()?;
+        if let Some(baseline) = (self.pointer[child.get()].baseline)(state) {
+            return Some(baseline);
+        }
+        let first = *self.children[child.get()].first()?;
+        Some(self.position[first.get()].y + self.baseline(first)?)
+    }
+
+    /// Returns the state of `child` if it is a `S`.
+    ///
+    /// This allows layouts to read per-child settings from wrapper components,
+    /// like `Linear` does with `Flexible`.
+    pub fn state<S: 'static>(&self, child: Cid) -> Option<&S> {
+        if !self.is_alive(child) {
+            return None;
+        }
+        self.state[child.get()].as_ref()?.downcast_ref()
+    }
+
     pub fn full_debug_name(&self) -> String {
         crate::full_debug_name_of(self.parent, self.name, self.current)
     }
+
+    fn is_alive(&self, child: Cid) -> bool {
+        let alive = crate::is_alive(self.generations, child);
+        if !alive {
+            log::error!(
+                "Layout of {} used the stale {:?} which no longer refers to a component",
+                self.full_debug_name(),
+                child
+            );
+        }
+        alive
+    }
 }
@@ -0,0 +1,247 @@
+use crate::ui::data::TypeIds;
+use crate::{Bounds, Cid, Component, Position, UiData};
+
+/// Looks up components, e.g. to make assertions in tests or for an inspector.
+///
+/// Every component is described by a path like `/Root/Padding/Linear/Button`.
+/// Each segment is the name of its `Iid` or, for unnamed `Iid`s, the name of its type.
+pub struct Query<'a> {
+    data: &'a UiData,
+    root: Cid,
+}
+
+/// A component found by a `Query`.
+#[derive(Clone, Copy)]
+pub struct Node<'a> {
+    data: &'a UiData,
+    cid: Cid,
+}
+
+impl<'a> Query<'a> {
+    pub(crate) fn new(data: &'a UiData, root: Cid) -> Self {
+        Query { data, root }
+    }
+
+    pub fn root(&self) -> Node<'a> {
+        Node {
+            data: self.data,
+            cid: self.root,
+        }
+    }
+
+    /// The component behind `cid`, unless it has been removed.
+    pub fn get(&self, cid: Cid) -> Option<Node<'a>> {
+        if self.data.is_alive(cid) && !self.data.is_fresh(cid) {
+            Some(Node {
+                data: self.data,
+                cid,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// All components in depth first order, starting at the root.
+    pub fn all(&self) -> Vec<Node<'a>> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(cid) = stack.pop() {
+            nodes.push(Node {
+                data: self.data,
+                cid,
+            });
+            stack.extend(self.data.children[cid.get()].iter().rev());
+        }
+        nodes
+    }
+
+    /// All components whose path matches the `glob`.
+    ///
+    /// In the glob `*` matches any part of a segment and `**` any number of segments,
+    /// so `**/Button` finds all `Button`s and `/Root/*/Text*` all `Text`s and `TextEdit`s
+    /// two levels below the root.
+    pub fn path(&self, glob: &str) -> Vec<Node<'a>> {
+        let pattern: Vec<&str> = glob.split('/').filter(|s| !s.is_empty()).collect();
+        self.all()
+            .into_iter()
+            .filter(|node| matches_path(&pattern, &node.segments()))
+            .collect()
+    }
+
+    /// All components of the type `Comp`.
+    ///
+    /// Like contexts, components are told apart by their `State`, `Msg` and `Event` types,
+    /// so this also finds components that share all three with `Comp`.
+    pub fn of_type<Comp: Component>(&self) -> Vec<Node<'a>> {
+        let typeids = TypeIds::of::<Comp>();
+        self.all()
+            .into_iter()
+            .filter(|node| self.data.typeids[node.cid.get()] == typeids)
+            .collect()
+    }
+
+    /// All components whose `Iid` has been named `name`, as in `iid!(name)`.
+    pub fn named(&self, name: &str) -> Vec<Node<'a>> {
+        self.all()
+            .into_iter()
+            .filter(|node| node.name() == name)
+            .collect()
+    }
+}
+
+impl<'a> Node<'a> {
+    pub fn cid(&self) -> Cid {
+        self.cid
+    }
+
+    /// The name of the component's `Iid`.
+    pub fn name(&self) -> &'static str {
+        self.data.name[self.cid.get()]
+    }
+
+    /// The full name of the component's type.
+    pub fn type_name(&self) -> &'static str {
+        self.data.pointer[self.cid.get()].type_name
+    }
+
+    /// The name of the component in paths.
+    pub fn segment(&self) -> &'static str {
+        match self.name() {
+            "Unnamed" | "" => short_type_name(self.type_name()),
+            name => name,
+        }
+    }
+
+    /// The path of this component, like `/Root/Padding/Linear/Button`.
+    pub fn path(&self) -> String {
+        self.segments()
+            .iter()
+            .fold(String::new(), |path, segment| path + "/" + segment)
+    }
+
+    fn segments(&self) -> Vec<&'static str> {
+        let mut segments = vec![self.segment()];
+        let mut current = self.cid;
+        while let Some(parent) = self.data.parent[current.get()] {
+            current = parent;
+            segments.push(
+                Node {
+                    cid: parent,
+                    ..*self
+                }
+                .segment(),
+            );
+        }
+        segments.reverse();
+        segments
+    }
+
+    /// The bounds relative to the window, as of the last `UiLayout` pass.
+    pub fn bounds(&self) -> Bounds {
+        let mut position = self.data.position[self.cid.get()];
+        let mut current = self.cid;
+        while let Some(parent) = self.data.parent[current.get()] {
+            current = parent;
+            position += self.data.position[parent.get()].to_vector();
+        }
+        Bounds::new(position, self.data.size[self.cid.get()])
+    }
+
+    /// The position relative to the parent.
+    pub fn position(&self) -> Position {
+        self.data.position[self.cid.get()]
+    }
+
+    /// The state of the component if it is a `S`.
+    pub fn state<S: 'static>(&self) -> Option<&'a S> {
+        self.data.state[self.cid.get()].as_ref()?.downcast_ref()
+    }
+
+    pub fn parent(&self) -> Option<Node<'a>> {
+        let cid = self.data.parent[self.cid.get()]?;
+        Some(Node { cid, ..*self })
+    }
+
+    pub fn children(&self) -> Vec<Node<'a>> {
+        self.data.children[self.cid.get()]
+            .iter()
+            .map(|&cid| Node { cid, ..*self })
+            .collect()
+    }
+}
+
+impl<'a> std::fmt::Debug for Node<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("cid", &self.cid)
+            .field("path", &self.path())
+            .field("bounds", &self.bounds())
+            .finish()
+    }
+}
+
+/// Strips the module path and generics from a type name.
+fn short_type_name(type_name: &'static str) -> &'static str {
+    let without_generics = type_name.split('<').next().unwrap_or(type_name);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+}
+
+fn matches_path(pattern: &[&str], segments: &[&str]) -> bool {
+    match (pattern.first(), segments.first()) {
+        (None, None) => true,
+        (Some(&"**"), _) => {
+            matches_path(&pattern[1..], segments)
+                || (!segments.is_empty() && matches_path(pattern, &segments[1..]))
+        }
+        (Some(glob), Some(segment)) => {
+            matches_segment(glob, segment) && matches_path(&pattern[1..], &segments[1..])
+        }
+        _ => false,
+    }
+}
+
+fn matches_segment(glob: &str, segment: &str) -> bool {
+    match glob.find('*') {
+        None => glob == segment,
+        Some(star) => {
+            let (prefix, rest) = (&glob[..star], &glob[star + 1..]);
+            if !segment.starts_with(prefix) {
+                return false;
+            }
+            let segment = &segment[prefix.len()..];
+            (0..=segment.len())
+                .filter(|&i| segment.is_char_boundary(i))
+                .any(|i| matches_segment(rest, &segment[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matches_path() {
+        let path = ["Root", "Padding", "Linear", "Button"];
+        assert!(matches_path(
+            &["Root", "Padding", "Linear", "Button"],
+            &path
+        ));
+        assert!(matches_path(&["**", "Button"], &path));
+        assert!(matches_path(&["**"], &path));
+        assert!(matches_path(&["Root", "*", "**", "But*"], &path));
+        assert!(matches_path(&["**", "Lin*r", "**"], &path));
+        assert!(!matches_path(&["Root", "*", "Button"], &path));
+        assert!(!matches_path(&["**", "Linear"], &path));
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("coat::widgets::text::Text<'_>"), "Text");
+        assert_eq!(short_type_name("app::Button"), "Button");
+        assert_eq!(short_type_name("Root"), "Root");
+    }
+}
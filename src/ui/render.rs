@@ -2,11 +2,11 @@ use crate::{Bounds, Cid, Position, Renderer, UiData};
 
 pub struct UiRender<'a> {
     data: &'a UiData,
-    renderer: &'a mut Renderer,
+    renderer: &'a mut dyn Renderer,
 }
 
 impl<'a> UiRender<'a> {
-    pub(crate) fn run(data: &'a UiData, renderer: &mut Renderer, root: Cid) {
+    pub(crate) fn run(data: &'a UiData, renderer: &mut dyn Renderer, root: Cid) {
         log::trace!("Running `UiRender`");
         let mut ui = UiRender { data, renderer };
         ui.render(root, Position::zero());
@@ -18,9 +18,14 @@ impl<'a> UiRender<'a> {
         let size = self.data.size[cid.get()];
         let bounds = Bounds::new(position, size);
 
-        {
+        let clip = {
             let state = self.data.state[cid.get()].as_ref().unwrap();
             (pointer.render)(state, bounds, self.renderer);
+            (pointer.clip)(state, bounds)
+        };
+
+        if let Some(clip) = clip {
+            self.renderer.push_clip(clip);
         }
 
         let children = &self.data.children[cid.get()];
@@ -28,5 +33,9 @@ impl<'a> UiRender<'a> {
         for child in children {
             self.render(*child, position);
         }
+
+        if clip.is_some() {
+            self.renderer.pop_clip();
+        }
     }
 }
@@ -0,0 +1,161 @@
+use crate::Cid;
+use fnv::FnvHashMap;
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Pushes the output of a finished task into the `Vec<Component::Msg>` of its owner.
+type Delivery = Box<dyn FnOnce(&mut Box<dyn Any>)>;
+
+/// Called whenever a task has been woken, so the backend can run its loop.
+pub(crate) type WakeHook = Arc<dyn Fn() + Send + Sync>;
+
+struct Task {
+    owner: Cid,
+    future: Pin<Box<dyn Future<Output = Delivery>>>,
+    waker: Arc<TaskWaker>,
+}
+
+/// A tiny executor for the futures started by `UiUpdate::spawn`.
+///
+/// All futures are polled on the ui thread, only the wake-ups may come from elsewhere.
+#[derive(Default)]
+pub(crate) struct Tasks {
+    tasks: FnvHashMap<usize, Task>,
+    next_id: usize,
+    ready: Arc<Mutex<Vec<usize>>>,
+    hook: Option<WakeHook>,
+}
+
+impl Tasks {
+    /// Sets the hook that is called whenever a task is ready to make progress.
+    pub(crate) fn set_wake_hook(&mut self, hook: WakeHook) {
+        self.hook = Some(hook);
+    }
+
+    pub(crate) fn spawn<F, M>(
+        &mut self,
+        owner: Cid,
+        future: F,
+        map: impl FnOnce(F::Output) -> M + 'static,
+    ) where
+        F: Future + 'static,
+        M: 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let future = async move {
+            let msg = map(future.await);
+            Box::new(move |messages: &mut Box<dyn Any>| {
+                messages.downcast_mut::<Vec<M>>().unwrap().push(msg);
+            }) as Delivery
+        };
+        let waker = Arc::new(TaskWaker {
+            id,
+            ready: self.ready.clone(),
+            hook: self.hook.clone(),
+        });
+
+        self.tasks.insert(
+            id,
+            Task {
+                owner,
+                future: Box::pin(future),
+                waker: waker.clone(),
+            },
+        );
+        // Every task gets polled at least once
+        waker.wake();
+    }
+
+    /// Drops all tasks that have been spawned by `owner`.
+    pub(crate) fn cancel(&mut self, owner: Cid) {
+        self.tasks.retain(|_, task| task.owner != owner);
+    }
+
+    /// Polls all woken tasks and delivers the messages of the finished ones.
+    /// Returns whether any message has been delivered.
+    pub(crate) fn poll(&mut self, messages: &mut [Option<Box<dyn Any>>]) -> bool {
+        let ready = std::mem::take(&mut *self.ready.lock().unwrap());
+        let mut delivered = false;
+
+        for id in ready {
+            let task = match self.tasks.get_mut(&id) {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let waker = Waker::from(task.waker.clone());
+            let mut context = Context::from_waker(&waker);
+            if let Poll::Ready(delivery) = task.future.as_mut().poll(&mut context) {
+                let owner = task.owner;
+                self.tasks.remove(&id);
+                match messages[owner.get()].as_mut() {
+                    Some(messages) => {
+                        delivery(messages);
+                        delivered = true;
+                    }
+                    None => log::error!("Tried to deliver a task result during an update"),
+                }
+            }
+        }
+
+        delivered
+    }
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<Vec<usize>>>,
+    hook: Option<WakeHook>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.lock().unwrap().push(self.id);
+        if let Some(hook) = &self.hook {
+            hook();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ui::test_messages;
+
+    #[test]
+    fn test_delivers_output() {
+        let mut tasks = Tasks::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+
+        tasks.spawn(owner, async { 20u32 }, |output| output + 1);
+        assert!(tasks.poll(&mut messages));
+
+        let delivered = messages[0].as_ref().unwrap().downcast_ref::<Vec<u32>>();
+        assert_eq!(delivered, Some(&vec![21]));
+        assert!(tasks.tasks.is_empty());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut tasks = Tasks::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+
+        tasks.spawn(owner, std::future::pending::<u32>(), |output| output);
+        assert!(!tasks.poll(&mut messages));
+        assert_eq!(tasks.tasks.len(), 1);
+
+        tasks.cancel(owner);
+        assert!(tasks.tasks.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::Cid;
+use fnv::FnvHashMap;
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Pushes the message of a timer into the `Vec<Component::Msg>` of its owner.
+type Delivery = Box<dyn FnMut(&mut Box<dyn Any>)>;
+
+/// Cancels a timer started by `UiUpdate::after` or `UiUpdate::every`.
+///
+/// Dropping the handle does not cancel the timer.
+#[derive(Debug, Clone)]
+pub struct TimerHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TimerHandle {
+    pub(crate) fn new() -> Self {
+        TimerHandle {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+struct Timer {
+    owner: Cid,
+    deadline: Instant,
+    interval: Option<Duration>,
+    handle: TimerHandle,
+    deliver: Delivery,
+}
+
+/// The pending timers of all `Component`s.
+#[derive(Default)]
+pub(crate) struct Timers {
+    timers: FnvHashMap<usize, Timer>,
+    next_id: usize,
+}
+
+impl Timers {
+    /// Delivers `msg` to `owner` once, after `duration` has passed.
+    pub(crate) fn after<M: 'static>(
+        &mut self,
+        owner: Cid,
+        duration: Duration,
+        msg: M,
+    ) -> TimerHandle {
+        let mut msg = Some(msg);
+        self.add(owner, duration, None, move |messages| {
+            if let Some(msg) = msg.take() {
+                messages.downcast_mut::<Vec<M>>().unwrap().push(msg);
+            }
+        })
+    }
+
+    /// Delivers `msg` to `owner` every `interval` until it gets cancelled.
+    pub(crate) fn every<M: Clone + 'static>(
+        &mut self,
+        owner: Cid,
+        interval: Duration,
+        msg: M,
+    ) -> TimerHandle {
+        self.add(owner, interval, Some(interval), move |messages| {
+            messages.downcast_mut::<Vec<M>>().unwrap().push(msg.clone());
+        })
+    }
+
+    fn add(
+        &mut self,
+        owner: Cid,
+        duration: Duration,
+        interval: Option<Duration>,
+        deliver: impl FnMut(&mut Box<dyn Any>) + 'static,
+    ) -> TimerHandle {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let handle = TimerHandle::new();
+        self.timers.insert(
+            id,
+            Timer {
+                owner,
+                deadline: Instant::now() + duration,
+                interval,
+                handle: handle.clone(),
+                deliver: Box::new(deliver),
+            },
+        );
+        handle
+    }
+
+    /// Drops all timers that have been started by `owner`.
+    pub(crate) fn cancel(&mut self, owner: Cid) {
+        self.timers.retain(|_, timer| timer.owner != owner);
+    }
+
+    /// The point in time at which the next timer fires.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .values()
+            .filter(|timer| !timer.handle.is_cancelled())
+            .map(|timer| timer.deadline)
+            .min()
+    }
+
+    /// Delivers the messages of all timers that are due at `now`.
+    /// Returns whether any message has been delivered.
+    pub(crate) fn poll(&mut self, now: Instant, messages: &mut [Option<Box<dyn Any>>]) -> bool {
+        self.timers.retain(|_, timer| !timer.handle.is_cancelled());
+
+        let mut delivered = false;
+        let mut finished = Vec::new();
+        for (id, timer) in self.timers.iter_mut() {
+            if timer.deadline > now {
+                continue;
+            }
+
+            match messages[timer.owner.get()].as_mut() {
+                Some(messages) => {
+                    (timer.deliver)(messages);
+                    delivered = true;
+                }
+                None => log::error!("Tried to deliver a timer message during an update"),
+            }
+
+            match timer.interval {
+                // Skip the ticks that have been missed instead of firing them all at once
+                Some(interval) if timer.deadline + interval > now => timer.deadline += interval,
+                Some(interval) => timer.deadline = now + interval,
+                None => finished.push(*id),
+            }
+        }
+
+        for id in finished {
+            self.timers.remove(&id);
+        }
+
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ui::test_messages;
+
+    fn delivered(messages: &[Option<Box<dyn Any>>]) -> &Vec<u32> {
+        messages[0].as_ref().unwrap().downcast_ref().unwrap()
+    }
+
+    #[test]
+    fn test_after_and_every() {
+        let mut timers = Timers::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+
+        timers.after(owner, second, 1u32);
+        timers.every(owner, second * 2, 2u32);
+        assert!(timers.next_deadline().unwrap() >= start + second);
+
+        assert!(!timers.poll(start, &mut messages));
+        assert!(timers.poll(start + second * 3, &mut messages));
+        assert!(timers.poll(start + second * 5, &mut messages));
+
+        let mut result = delivered(&messages).clone();
+        result.sort();
+        assert_eq!(result, vec![1, 2, 2]);
+        assert_eq!(timers.timers.len(), 1);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut timers = Timers::default();
+        let mut messages = test_messages();
+        let owner = Cid::new(0, 0);
+        let later = Instant::now() + Duration::from_secs(10);
+
+        let handle = timers.every(owner, Duration::from_secs(1), 1u32);
+        timers.after(owner, Duration::from_secs(1), 2u32);
+        handle.cancel();
+        assert!(timers.poll(later, &mut messages));
+        assert_eq!(delivered(&messages), &vec![2]);
+
+        timers.every(owner, Duration::from_secs(1), 3u32);
+        timers.cancel(owner);
+        assert!(timers.next_deadline().is_none());
+    }
+}
@@ -1,5 +1,10 @@
-use crate::{Cid, Component, ComponentPointer, Font, Renderer, TypeIds, UiData};
+use crate::{
+    find_ancestor, find_context, Cid, Component, ComponentPointer, Font, Frames, Renderer, Tasks,
+    TimerHandle, Timers, TypeIds, UiData,
+};
 use std::any::{Any, TypeId};
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 pub struct UiUpdate<'a> {
     typeids: &'a Vec<TypeIds>,
@@ -10,7 +15,10 @@ pub struct UiUpdate<'a> {
     events: &'a mut Vec<Box<Any>>,
     state: &'a mut Vec<Option<Box<Any>>>,
     focused: &'a mut Option<Cid>,
-    renderer: &'a mut Renderer,
+    tasks: &'a mut Tasks,
+    timers: &'a mut Timers,
+    frames: &'a mut Frames,
+    renderer: &'a mut dyn Renderer,
     cid: Cid,
     needs_update: bool,
 }
@@ -39,19 +47,89 @@ impl<'a> UiUpdate<'a> {
     /// TODO: Can this be removed?
     /// Sends the `msg` to the closest parent of the related `Component`
     pub fn bubble<Target: Component>(&mut self, msg: Target::Msg) {
-        while let Some(parent) = self.parent[self.cid.get()] {
-            if self.typeids[parent.get()] == TypeIds::of::<Target>() {
-                let messages: &mut Vec<Target::Msg> = self.messages[parent.get()]
-                    .as_mut()
-                    .unwrap()
-                    .downcast_mut()
-                    .unwrap();
-                messages.push(msg);
-                self.needs_update();
-                return;
-            }
+        let target = TypeIds::of::<Target>();
+        if let Some(parent) = find_ancestor(self.parent, self.typeids, self.cid, target) {
+            let messages: &mut Vec<Target::Msg> = self.messages[parent.get()]
+                .as_mut()
+                .unwrap()
+                .downcast_mut()
+                .unwrap();
+            messages.push(msg);
+            self.needs_update();
+        } else {
+            log::warn!("Tried to bubble a message but the targeted Component does not exist");
+        }
+    }
+
+    /// Returns the state of the closest ancestor which is a `Provider`.
+    ///
+    /// Changes to the state of the `Provider` will cause a new `UiView` pass,
+    /// so all components reading the context will be updated.
+    pub fn context<Provider: Component>(&self) -> Option<&Provider::State> {
+        find_context::<Provider>(self.parent, self.typeids, self.state, self.cid, None)
+    }
+
+    /// Runs `future` and sends its output, mapped to a message by `map`, back to this component.
+    ///
+    /// The future is dropped if the component gets removed before it finished.
+    pub fn spawn<F, M>(&mut self, future: F, map: impl FnOnce(F::Output) -> M + 'static)
+    where
+        F: Future + 'static,
+        M: 'static,
+    {
+        if self.typeids[self.cid.get()].message == TypeId::of::<M>() {
+            self.tasks.spawn(self.cid, future, map);
+        } else {
+            log::error!("Tried to spawn a future with the wrong message type");
+        }
+    }
+
+    /// Sends `msg` to this component once `duration` has passed.
+    pub fn after<M: 'static>(&mut self, duration: Duration, msg: M) -> TimerHandle {
+        if self.typeids[self.cid.get()].message == TypeId::of::<M>() {
+            self.timers.after(self.cid, duration, msg)
+        } else {
+            log::error!("Tried to start a timer with the wrong message type");
+            let handle = TimerHandle::new();
+            handle.cancel();
+            handle
         }
-        log::warn!("Tried to bubble a message but the targeted Component does not exist");
+    }
+
+    /// Sends `msg` to this component every `interval` until the timer gets cancelled.
+    pub fn every<M: Clone + 'static>(&mut self, interval: Duration, msg: M) -> TimerHandle {
+        if self.typeids[self.cid.get()].message == TypeId::of::<M>() {
+            self.timers.every(self.cid, interval, msg)
+        } else {
+            log::error!("Tried to start a timer with the wrong message type");
+            let handle = TimerHandle::new();
+            handle.cancel();
+            handle
+        }
+    }
+
+    /// Sends `msg` to this component at the beginning of the next frame.
+    ///
+    /// Once no frames are requested anymore the event loop goes back to sleep.
+    pub fn request_frame<M: 'static>(&mut self, msg: M) {
+        if self.typeids[self.cid.get()].message == TypeId::of::<M>() {
+            self.frames.request(self.cid, msg);
+        } else {
+            log::error!("Tried to request a frame with the wrong message type");
+        }
+    }
+
+    /// Calls `Component::tick` of this component at the beginning of the next frame.
+    ///
+    /// Animations request a tick again from `tick` for as long as they are running,
+    /// which keeps the event loop running just like `request_frame`.
+    pub fn request_tick(&mut self) {
+        self.frames.request_tick(self.cid);
+    }
+
+    /// The time at which the current frame began.
+    pub fn frame_time(&self) -> Instant {
+        self.frames.time()
     }
 
     pub fn add_font(&mut self, font: &Font, data: impl Into<Vec<u8>>) {
@@ -70,7 +148,7 @@ impl<'a> UiUpdate<'a> {
         self.needs_update = true;
     }
 
-    pub(crate) fn run(data: &'a mut UiData, renderer: &'a mut Renderer, root: Cid) -> bool {
+    pub(crate) fn run(data: &'a mut UiData, renderer: &'a mut dyn Renderer, root: Cid) -> bool {
         if data.is_fresh(root) {
             log::trace!("Skipping `UiUpdate`");
             return true;
@@ -86,6 +164,9 @@ impl<'a> UiUpdate<'a> {
             events: &mut data.events,
             state: &mut data.state,
             focused: &mut data.focused,
+            tasks: &mut data.tasks,
+            timers: &mut data.timers,
+            frames: &mut data.frames,
             renderer: renderer,
             cid: root,
             needs_update: false,
@@ -101,6 +182,9 @@ impl<'a> UiUpdate<'a> {
             let mut state = self.state[cid.get()].take().unwrap();
 
             let pointer = self.pointer[cid.get()];
+            if self.frames.take_tick(cid) {
+                (pointer.tick)(&mut state, self);
+            }
             (pointer.update)(&mut messages, &mut state, self);
 
             self.messages[cid.get()] = Some(messages);
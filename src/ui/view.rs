@@ -1,7 +1,7 @@
 use crate::component::ComponentPointerTrait;
 use crate::{
     find_focus_state, Cid, Component, ContentBuilder, FocusState, Iid, Renderer, TypeIds, UiData,
-    UiDerive,
+    UiDerive, Viewing,
 };
 use std::cell::Cell;
 use std::marker::PhantomData;
@@ -17,7 +17,9 @@ pub struct UiView<'a, Comp: Component> {
     cid: Cid,
     /// Type of the component behind `cid`.
     marker: PhantomData<Comp>,
-    renderer: &'a mut Renderer,
+    renderer: &'a mut dyn Renderer,
+    /// The states of `cid` and all the components that are creating it.
+    viewing: Option<&'a Viewing<'a>>,
 }
 
 impl<'a, Comp: Component> UiView<'a, Comp> {
@@ -29,7 +31,24 @@ impl<'a, Comp: Component> UiView<'a, Comp> {
         }
     }
 
-    pub(crate) fn new(data: &'a mut UiData, renderer: &'a mut Renderer, cid: Cid) -> Self {
+    /// Returns the state of the closest ancestor which is a `Provider`.
+    ///
+    /// As every `UiView` pass runs the `view` functions of all components,
+    /// changes to the state of the `Provider` will always be picked up.
+    ///
+    /// ```ignore
+    /// let theme = ui.context::<ThemeProvider>().cloned().unwrap_or_default();
+    /// ```
+    pub fn context<Provider: Component>(&self) -> Option<&Provider::State> {
+        self.data.context::<Provider>(self.cid, self.viewing)
+    }
+
+    pub(crate) fn new(
+        data: &'a mut UiData,
+        renderer: &'a mut dyn Renderer,
+        cid: Cid,
+        viewing: Option<&'a Viewing<'a>>,
+    ) -> Self {
         let parent = Rc::new(Cell::new(None));
         UiView {
             data,
@@ -37,40 +56,61 @@ impl<'a, Comp: Component> UiView<'a, Comp> {
             cid,
             marker: PhantomData,
             renderer,
+            viewing,
         }
     }
 
     /// This will construct a `UiView` with the same date but for a different component.
-    fn another<AComp: Component>(&mut self, cid: Cid) -> UiView<AComp> {
+    fn another<'b, AComp: Component>(
+        &'b mut self,
+        cid: Cid,
+        viewing: &'b Viewing<'b>,
+    ) -> UiView<'b, AComp> {
         UiView {
             data: self.data,
             parent: self.parent.clone(),
-            cid: cid,
+            cid,
             marker: PhantomData,
             renderer: self.renderer,
+            viewing: Some(viewing),
         }
     }
 
-    pub(crate) fn run(data: &'a mut UiData, renderer: &'a mut Renderer, app_id: Cid, props: Comp) {
+    pub(crate) fn run(
+        data: &'a mut UiData,
+        renderer: &'a mut dyn Renderer,
+        app_id: Cid,
+        props: Comp,
+    ) {
         log::trace!("Running `UiView`");
-        if data.typeids[app_id.get()] == TypeIds::void() {
+        if data.is_fresh(app_id) {
             log::trace!("Initializing root component with {:?}", app_id);
             data.typeids[app_id.get()] = TypeIds::of::<Comp>();
             data.name[app_id.get()] = "Root";
             data.pointer[app_id.get()] = Comp::pointer();
             data.state[app_id.get()] = Some(Box::new(Comp::init(&props)));
-            data.messages[app_id.get()] = Some(Box::new(Vec::<Comp::Msg>::new()));
-            data.events[app_id.get()] = Box::new(Vec::<Comp::Event>::new());
+            data.messages[app_id.get()] = Some(Box::<Vec<Comp::Msg>>::default());
+            data.events[app_id.get()] = Box::<Vec<Comp::Event>>::default();
 
             log::trace!("Root component set: {}", data.full_debug_name_of(app_id));
         }
 
+        data.begin_view_pass();
+        data.attach(app_id);
+
         let state = data.state[app_id.get()].take().unwrap();
         {
-            let mut ui = UiView::new(data, renderer, app_id);
+            let viewing = Viewing {
+                cid: app_id,
+                state: &*state,
+                outer: None,
+            };
+            let mut ui = UiView::new(data, renderer, app_id, Some(&viewing));
             Comp::view(&props, state.downcast_ref().unwrap(), &mut ui);
         }
         data.state[app_id.get()] = Some(state);
+
+        data.sweep(app_id);
     }
 
     /// Adds a new component to the tree.
@@ -80,34 +120,45 @@ impl<'a, Comp: Component> UiView<'a, Comp> {
         props: NewComp,
         iid: Iid,
     ) -> ContentBuilder<NewComp, Comp> {
-        let tid = iid.id;
+        // The type is part of the key, so colliding `Iid`s never share their state
+        let tid = (iid.combined(), TypeIds::of::<NewComp>());
         let name = iid.name.unwrap_or("Unnamed");
-        let cid = self.data.creations[self.cid.get()]
-            .get(&tid)
-            .cloned()
-            .unwrap_or_else(|| {
-                let cid = self.data.fresh_id();
-                log::trace!("Initializing component \"{}\" with {:?}", name, cid);
-                self.data.creations[self.cid.get()].insert(tid, cid);
-
-                let parent = self.parent.get().unwrap_or(self.cid);
-
-                self.data.typeids[cid.get()] = TypeIds::of::<NewComp>();
-                self.data.name[cid.get()] = name;
-                self.data.pointer[cid.get()] = NewComp::pointer();
-                self.data.parent[cid.get()] = Some(parent);
-                self.data.children[parent.get()].push(cid);
-                self.data.state[cid.get()] = Some(Box::new(NewComp::init(&props)));
-                self.data.messages[cid.get()] = Some(Box::new(Vec::<NewComp::Msg>::new()));
-                self.data.events[cid.get()] = Box::new(Vec::<NewComp::Event>::new());
-
-                log::trace!(
-                    "Initial component set: {}",
-                    self.data.full_debug_name_of(cid)
-                );
-
-                cid
-            });
+        let existing = self.data.creations[self.cid.get()].get(&tid).cloned();
+        let cid = existing.unwrap_or_else(|| {
+            if cfg!(debug_assertions) {
+                let creations = &self.data.creations[self.cid.get()];
+                if let Some(other) = creations.keys().find(|key| key.0 == tid.0) {
+                    log::error!(
+                        "Component \"{}\" collides with the differently typed component {}. \
+                             Use distinct `Iid`s or keys to avoid this.",
+                        name,
+                        self.data.full_debug_name_of(creations[other])
+                    );
+                }
+            }
+
+            let cid = self.data.fresh_id();
+            log::trace!("Initializing component \"{}\" with {:?}", name, cid);
+            self.data.creations[self.cid.get()].insert(tid, cid);
+
+            let parent = self.parent.get().unwrap_or(self.cid);
+
+            self.data.typeids[cid.get()] = TypeIds::of::<NewComp>();
+            self.data.name[cid.get()] = name;
+            self.data.pointer[cid.get()] = NewComp::pointer();
+            self.data.parent[cid.get()] = Some(parent);
+            self.data.state[cid.get()] = Some(Box::new(NewComp::init(&props)));
+            self.data.messages[cid.get()] = Some(Box::<Vec<NewComp::Msg>>::default());
+            self.data.events[cid.get()] = Box::<Vec<NewComp::Event>>::default();
+
+            log::trace!(
+                "Initial component set: {}",
+                self.data.full_debug_name_of(cid)
+            );
+
+            cid
+        });
+        self.data.attach(cid);
 
         let mut state = self.data.state[cid.get()].take().unwrap();
         {
@@ -116,12 +167,22 @@ impl<'a, Comp: Component> UiView<'a, Comp> {
 
             {
                 let state = state.downcast_mut().unwrap();
-                let font_manager = &mut self.renderer.font_manager;
-                let ui = UiDerive::new(font_manager);
+                let font_manager = self.renderer.fonts();
+                let ui = UiDerive::new(font_manager, self.data, cid, self.viewing);
                 NewComp::derive_state(&props, state, &ui);
+                let (requests, tick) = ui.into_frame_requests();
+                self.data.frames.extend(requests);
+                if tick {
+                    self.data.frames.request_tick(cid);
+                }
             }
 
-            let mut ui = self.another(cid);
+            let viewing = Viewing {
+                cid,
+                state: &*state,
+                outer: self.viewing,
+            };
+            let mut ui = self.another(cid, &viewing);
             NewComp::view(&props, state.downcast_ref().unwrap(), &mut ui);
 
             self.parent.set(current_parent);
@@ -138,6 +199,10 @@ impl<'a, Comp: Component> UiView<'a, Comp> {
         Emitter: Component,
         Handler: Fn(Emitter::Event) -> Option<Comp::Msg>,
     {
+        if !self.data.is_alive(emitter) {
+            log::error!("Tried to map the events of the stale {:?}", emitter);
+            return;
+        }
         let events: &mut Vec<Emitter::Event> =
             self.data.events[emitter.get()].downcast_mut().unwrap();
         let messages: &mut Vec<Comp::Msg> = self.data.messages[self.cid.get()]
@@ -1,6 +1,15 @@
 mod rectangle;
 pub use self::rectangle::Rectangle;
 
+mod animated_rectangle;
+pub use self::animated_rectangle::AnimatedRectangle;
+
+mod image;
+pub use self::image::Image;
+
+mod clip;
+pub use self::clip::Clip;
+
 mod glyphs;
 pub use self::glyphs::Glyphs;
 
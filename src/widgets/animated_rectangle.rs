@@ -0,0 +1,58 @@
+use crate::{
+    Animated, Bounds, BoxConstraints, Cid, Color, Component, Mut, Rectangle, Renderer, Size,
+    Transition, UiDerive, UiLayout, UiUpdate,
+};
+
+/// A `Rectangle` that fades to its new color whenever it changes.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct AnimatedRectangle {
+    color: Color,
+    transition: Transition,
+}
+
+impl AnimatedRectangle {
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+}
+
+impl Component for AnimatedRectangle {
+    type State = Animated<Color>;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        Animated::new(props.color).transition(props.transition)
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, ui: &UiDerive) {
+        state.set_transition(props.transition);
+        if props.color != state.target() {
+            state.set(props.color, ui.frame_time());
+            ui.request_tick();
+        }
+    }
+
+    fn tick(mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.drive(ui);
+    }
+
+    fn layout(
+        _state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        Rectangle::layout(&Rectangle::default(), children, constraints, ui)
+    }
+
+    fn render(state: &Self::State, bounds: Bounds, renderer: &mut dyn Renderer) {
+        renderer.rect(bounds, state.value());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{Bounds, Component};
+
+/// Prevents its content from being drawn outside of its own bounds.
+#[derive(Default)]
+pub struct Clip;
+
+impl Component for Clip {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn clip(_state: &Self::State, bounds: Bounds) -> Option<Bounds> {
+        Some(bounds)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Bitmap, Bounds, BoxConstraints, Cid, Component, Renderer, Size, UiDerive, UiLayout};
+
+/// Draws a `Bitmap`, it tries to keep the size of the `Bitmap`
+/// but will be stretched to fit its constraints.
+#[derive(Default)]
+pub struct Image<'a> {
+    bitmap: Option<&'a Bitmap>,
+}
+
+impl<'a> Image<'a> {
+    pub fn bitmap(mut self, bitmap: &'a Bitmap) -> Self {
+        self.bitmap = Some(bitmap);
+        self
+    }
+}
+
+pub struct ImageState {
+    bitmap: Option<Bitmap>,
+}
+
+impl<'a> Component for Image<'a> {
+    type State = ImageState;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        ImageState {
+            bitmap: props.bitmap.cloned(),
+        }
+    }
+
+    fn derive_state(props: &Self, state: &mut Self::State, _ui: &UiDerive) {
+        if props.bitmap != state.bitmap.as_ref() {
+            state.bitmap = props.bitmap.cloned();
+        }
+    }
+
+    fn layout(
+        state: &Self::State,
+        children: &[Cid],
+        constraints: BoxConstraints,
+        ui: &mut UiLayout,
+    ) -> Size {
+        if !children.is_empty() {
+            let name = ui.full_debug_name();
+            log::error!(
+                "The primitive Component {} has content attached to it but it will be ignored",
+                name
+            );
+        }
+
+        let size = state
+            .bitmap
+            .as_ref()
+            .map(Bitmap::size)
+            .unwrap_or_else(Size::zero);
+        constraints.check_size(size)
+    }
+
+    fn render(state: &Self::State, bounds: Bounds, renderer: &mut dyn Renderer) {
+        if let Some(bitmap) = &state.bitmap {
+            renderer.image(bounds, bitmap);
+        }
+    }
+}
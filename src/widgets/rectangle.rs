@@ -47,20 +47,7 @@ impl Component for Rectangle {
         )
     }
 
-    fn render(state: &Self::State, bounds: Bounds, renderer: &mut Renderer) {
-        use webrender::api::*;
-
-        let position = bounds.origin;
-        let size = bounds.size;
-        let color = state.color;
-
-        let info = LayoutPrimitiveInfo::new(LayoutRect::new(
-            LayoutPoint::new(position.x, position.y),
-            LayoutSize::new(size.width, size.height),
-        ));
-
-        renderer
-            .builder
-            .push_rect(&info, ColorF::new(color.r, color.g, color.b, color.a));
+    fn render(state: &Self::State, bounds: Bounds, renderer: &mut dyn Renderer) {
+        renderer.rect(bounds, state.color);
     }
 }
@@ -0,0 +1,296 @@
+use coat::backend::headless::Headless;
+use coat::*;
+use std::time::Duration;
+
+#[derive(Default)]
+struct App;
+
+struct State {
+    buffer: Buffer,
+    clicks: usize,
+}
+
+enum Msg {
+    Edit(BufferUpdate),
+    Clicked,
+}
+
+#[derive(Debug, PartialEq)]
+enum AppEvent {
+    Clicked(usize),
+}
+
+impl Component for App {
+    type State = State;
+    type Msg = Msg;
+    type Event = AppEvent;
+
+    fn init(_props: &Self) -> Self::State {
+        State {
+            buffer: Buffer::default(),
+            clicks: 0,
+        }
+    }
+
+    fn update(msg: Self::Msg, mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        match msg {
+            Msg::Edit(update) => state.buffer.update(update),
+            Msg::Clicked => {
+                state.clicks += 1;
+                ui.emit(AppEvent::Clicked(state.clicks));
+            }
+        }
+    }
+
+    fn view(_props: &Self, state: &Self::State, ui: &mut UiView<Self>) {
+        Linear::new().vertical().set(iid!(), ui).add(|| {
+            Constrained::new()
+                .min_height(20.0)
+                .max_height(20.0)
+                .set(iid!(), ui)
+                .add(|| {
+                    TextEdit::new()
+                        .buffer(&state.buffer)
+                        .size(14)
+                        .set(iid!(), ui)
+                        .map_events(ui, |update| Some(Msg::Edit(update)));
+                });
+
+            Constrained::new().max_height(30.0).set(iid!(), ui).add(|| {
+                TouchArea::new()
+                    .set(iid!(), ui)
+                    .map_events(ui, |event| match event {
+                        TouchAreaEvent::Activated(MouseButton::Left) => Some(Msg::Clicked),
+                        _ => None,
+                    })
+                    .add(|| {
+                        Rectangle::new()
+                            .color(Color::rgb(0.3, 0.3, 0.7))
+                            .set(iid!(), ui);
+                    });
+            });
+        });
+    }
+}
+
+fn app() -> Headless<App> {
+    let mut app = Headless::new(Size::new(200.0, 100.0));
+    app.settle();
+    app
+}
+
+#[test]
+fn text_edit() {
+    let mut app = app();
+
+    app.type_text("ignored");
+    assert_eq!(app.state().unwrap().buffer.text(), "");
+
+    // An empty `TextEdit` is only as wide as its cursor
+    app.click(Position::new(1.0, 10.0));
+    app.type_text("abc");
+    app.press(VirtualKeyCode::Left);
+    app.type_text("X");
+    assert_eq!(app.state().unwrap().buffer.text(), "abXc");
+
+    app.press(VirtualKeyCode::Backspace);
+    assert_eq!(app.state().unwrap().buffer.text(), "abc");
+    assert_eq!(app.state().unwrap().buffer.cursor(), 2);
+}
+
+#[test]
+fn touch_area() {
+    let mut app = app();
+
+    app.click(Position::new(100.0, 35.0));
+    app.click(Position::new(100.0, 35.0));
+    app.click(Position::new(100.0, 80.0));
+    assert_eq!(
+        app.events(),
+        vec![AppEvent::Clicked(1), AppEvent::Clicked(2)]
+    );
+    assert!(app.events().is_empty());
+
+    app.send(Msg::Clicked);
+    assert_eq!(app.events(), vec![AppEvent::Clicked(3)]);
+    assert_eq!(app.state().unwrap().clicks, 3);
+}
+
+#[test]
+fn query() {
+    let app = app();
+    let query = app.query();
+
+    let touch_areas = query.of_type::<TouchArea>();
+    assert_eq!(touch_areas.len(), 1);
+    let bounds = touch_areas[0].bounds();
+    assert_eq!(bounds.origin, Position::new(0.0, 20.0));
+    assert_eq!(bounds.size, Size::new(200.0, 30.0));
+
+    let rectangles = query.path("/Root/Linear/Constrained/TouchArea/Rectangle");
+    assert_eq!(rectangles.len(), 1);
+    assert_eq!(rectangles[0].bounds(), bounds);
+    assert_eq!(rectangles[0].parent().unwrap().cid(), touch_areas[0].cid());
+    assert!(rectangles[0].state::<Rectangle>().is_some());
+
+    let edits = query.path("**/TextEdit");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].children().len(), 3);
+    assert!(edits[0].state::<State>().is_none());
+
+    assert_eq!(query.named("Cursor").len(), 1);
+    assert_eq!(query.root().state::<State>().unwrap().clicks, 0);
+}
+
+/// Remembers the value it has been created with, even if its properties change.
+#[derive(Default)]
+struct Remember {
+    value: u32,
+}
+
+impl Component for Remember {
+    type State = u32;
+    type Msg = ();
+    type Event = ();
+
+    fn init(props: &Self) -> Self::State {
+        props.value
+    }
+}
+
+#[derive(Default)]
+struct Keyed;
+
+impl Component for Keyed {
+    type State = Vec<u32>;
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {
+        vec![1, 2, 3]
+    }
+
+    fn update(_msg: Self::Msg, mut state: Mut<Self::State>, _ui: &mut UiUpdate) {
+        state.reverse();
+    }
+
+    fn view(_props: &Self, state: &Self::State, ui: &mut UiView<Self>) {
+        Linear::new().set(iid!(), ui).add(|| {
+            for value in state {
+                Remember { value: *value }.set(iid!(Item).key(value), ui);
+            }
+        });
+    }
+}
+
+#[test]
+fn keyed_reorder() {
+    let mut app = Headless::<Keyed>::new(Size::new(200.0, 100.0));
+    app.settle();
+
+    // The children of a `Linear` are kept in the order they have been set
+    let items = |app: &Headless<Keyed>| -> Vec<(Cid, u32)> {
+        let query = app.query();
+        let linear = query.of_type::<Linear>();
+        linear[0]
+            .children()
+            .iter()
+            .map(|node| (node.cid(), *node.state::<u32>().unwrap()))
+            .collect()
+    };
+    let before = items(&app);
+    assert_eq!(
+        before.iter().map(|it| it.1).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    app.send(());
+    let after = items(&app);
+    let mut reversed = before.clone();
+    reversed.reverse();
+    assert_eq!(after, reversed);
+}
+
+#[derive(Default)]
+struct Colliding;
+
+impl Component for Colliding {
+    type State = u32;
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {
+        7
+    }
+
+    fn update(_msg: Self::Msg, mut state: Mut<Self::State>, _ui: &mut UiUpdate) {
+        *state += 1;
+    }
+
+    fn view(_props: &Self, state: &Self::State, ui: &mut UiView<Self>) {
+        let same = iid!(Same);
+        Stack::new().set(iid!(), ui).add(|| {
+            // A recreated `Remember` would pick up the new value
+            Remember { value: *state }.set(same, ui);
+            Rectangle::new().set(same, ui);
+        });
+    }
+}
+
+#[test]
+fn colliding_iids() {
+    let mut app = Headless::<Colliding>::new(Size::new(200.0, 100.0));
+    app.settle();
+    let cids = |app: &Headless<Colliding>| -> Vec<Cid> {
+        app.query()
+            .named("Same")
+            .iter()
+            .map(|it| it.cid())
+            .collect()
+    };
+    let before = cids(&app);
+    assert_eq!(before.len(), 2);
+
+    // Both components survive further view passes with their state
+    app.send(());
+    app.send(());
+    assert_eq!(app.state(), Some(&9));
+    assert_eq!(cids(&app), before);
+    let remember = app.query().get(before[0]).unwrap();
+    assert_eq!(remember.state::<u32>(), Some(&7));
+}
+
+/// Animates to every value it is sent, driven by `Component::tick`.
+#[derive(Default)]
+struct Fade;
+
+impl Component for Fade {
+    type State = Animated<Scalar>;
+    type Msg = Scalar;
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {
+        Animated::new(0.0).tween(Duration::from_secs(0), Easing::Linear)
+    }
+
+    fn update(msg: Self::Msg, mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.animate_to(msg, ui);
+    }
+
+    fn tick(mut state: Mut<Self::State>, ui: &mut UiUpdate) {
+        state.drive(ui);
+    }
+}
+
+#[test]
+fn ticking() {
+    let mut app = Headless::<Fade>::new(Size::new(200.0, 100.0));
+    app.settle();
+
+    // `animate_to` leaves the value as it is, the tick of the next frame reaches the target
+    app.send(10.0);
+    let state = app.state().unwrap();
+    assert_eq!(state.value(), 10.0);
+    assert!(!state.is_animating());
+    assert!(!app.run());
+}
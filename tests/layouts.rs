@@ -0,0 +1,408 @@
+use coat::backend::headless::Headless;
+use coat::*;
+
+fn bounds_of<Comp: Component>(app: &Headless<Comp>, name: &str) -> Bounds {
+    let nodes = app.query().named(name);
+    assert_eq!(nodes.len(), 1, "expected exactly one {}", name);
+    nodes[0].bounds()
+}
+
+fn run<Comp: Component>(size: Size) -> Headless<Comp> {
+    let mut app = Headless::new(size);
+    app.settle();
+    app
+}
+
+fn rect(iid: Iid, ui: &mut UiView<impl Component>) {
+    Rectangle::new()
+        .color(Color::rgb(1.0, 1.0, 1.0))
+        .set(iid, ui);
+}
+
+#[derive(Default)]
+struct Flex;
+
+impl Component for Flex {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Linear::new().spacing(10.0).set(iid!(), ui).add(|| {
+            Expanded::new().flex(2.0).set(iid!(), ui).add(|| {
+                rect(iid!(Wide), ui);
+            });
+            Constrained::new().max_width(50.0).set(iid!(), ui).add(|| {
+                rect(iid!(Fixed), ui);
+            });
+            Flexible::new().set(iid!(), ui).add(|| {
+                Constrained::new().max_width(20.0).set(iid!(), ui).add(|| {
+                    rect(iid!(Loose), ui);
+                });
+            });
+            Expanded::new().set(iid!(), ui).add(|| {
+                rect(iid!(Narrow), ui);
+            });
+        });
+    }
+}
+
+#[test]
+fn linear_flex() {
+    let app = run::<Flex>(Size::new(330.0, 100.0));
+
+    // 330 - 3 * 10 spacing - 50 fixed leaves 250 for the flex factors 2, 1 and 1
+    assert_eq!(bounds_of(&app, "Fixed").size.width, 50.0);
+    assert_eq!(bounds_of(&app, "Wide").size.width, 125.0);
+    assert_eq!(bounds_of(&app, "Loose").size.width, 20.0);
+    assert_eq!(bounds_of(&app, "Narrow").size.width, 62.5);
+
+    assert_eq!(bounds_of(&app, "Fixed").origin.x, 135.0);
+    assert_eq!(bounds_of(&app, "Loose").origin.x, 195.0);
+    assert_eq!(bounds_of(&app, "Narrow").origin.x, 225.0);
+}
+
+#[derive(Default)]
+struct Alignments;
+
+impl Component for Alignments {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Linear::new()
+            .vertical()
+            .main_axis_alignment(MainAxisAlignment::SpaceBetween)
+            .cross_axis_alignment(CrossAxisAlignment::Stretch)
+            .set(iid!(), ui)
+            .add(|| {
+                Linear::new()
+                    .main_axis_alignment(MainAxisAlignment::SpaceEvenly)
+                    .cross_axis_alignment(CrossAxisAlignment::Center)
+                    .set(iid!(), ui)
+                    .add(|| {
+                        Constrained::new()
+                            .max(Size::new(45.0, 20.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Small), ui);
+                            });
+                        Constrained::new()
+                            .max(Size::new(35.0, 40.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Tall), ui);
+                            });
+                    });
+                Linear::new()
+                    .main_axis_alignment(MainAxisAlignment::End)
+                    .cross_axis_alignment(CrossAxisAlignment::End)
+                    .set(iid!(), ui)
+                    .add(|| {
+                        Constrained::new()
+                            .max(Size::new(30.0, 10.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Short), ui);
+                            });
+                        Constrained::new()
+                            .max(Size::new(30.0, 30.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Long), ui);
+                            });
+                    });
+            });
+    }
+}
+
+#[test]
+fn linear_alignment() {
+    let app = run::<Alignments>(Size::new(200.0, 100.0));
+
+    // The rows are stretched to the full width and pushed apart vertically
+    assert_eq!(bounds_of(&app, "Small").origin, Position::new(40.0, 10.0));
+    assert_eq!(bounds_of(&app, "Tall").origin, Position::new(125.0, 0.0));
+    assert_eq!(bounds_of(&app, "Short").origin, Position::new(140.0, 90.0));
+    assert_eq!(bounds_of(&app, "Long").origin, Position::new(170.0, 70.0));
+}
+
+#[derive(Default)]
+struct Aligns;
+
+impl Component for Aligns {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Linear::new()
+            .vertical()
+            .cross_axis_alignment(CrossAxisAlignment::Center)
+            .set(iid!(), ui)
+            .add(|| {
+                Expanded::new().set(iid!(), ui).add(|| {
+                    Align::new()
+                        .alignment(Alignment::new(1.0, 0.5))
+                        .set(iid!(), ui)
+                        .add(|| {
+                            Constrained::new()
+                                .max(Size::new(40.0, 20.0))
+                                .set(iid!(), ui)
+                                .add(|| {
+                                    rect(iid!(Aligned), ui);
+                                });
+                        });
+                });
+                Center::new()
+                    .width_factor(2.0)
+                    .height_factor(1.5)
+                    .set(iid!(Centered), ui)
+                    .add(|| {
+                        Constrained::new()
+                            .max(Size::new(40.0, 20.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Inner), ui);
+                            });
+                    });
+            });
+    }
+}
+
+#[test]
+fn align_and_center() {
+    let app = run::<Aligns>(Size::new(200.0, 100.0));
+
+    // The `Center` takes 30 of the height, which leaves 70 for the `Align`,
+    // and is centered horizontally by the `Linear`
+    assert_eq!(
+        bounds_of(&app, "Aligned").origin,
+        Position::new(160.0, 37.5)
+    );
+    assert_eq!(
+        bounds_of(&app, "Centered"),
+        Bounds::new(Position::new(60.0, 70.0), Size::new(80.0, 30.0))
+    );
+    assert_eq!(bounds_of(&app, "Inner").origin, Position::new(80.0, 75.0));
+}
+
+#[derive(Default)]
+struct Stacked;
+
+impl Component for Stacked {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Align::new()
+            .alignment(Alignment::TOP_LEFT)
+            .set(iid!(), ui)
+            .add(|| {
+                Stack::new()
+                    .alignment(Alignment::TOP_LEFT)
+                    .set(iid!(Stack), ui)
+                    .add(|| {
+                        Constrained::new()
+                            .max(Size::new(100.0, 60.0))
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Image), ui);
+                            });
+                        Aligned::new()
+                            .alignment(Alignment::CENTER)
+                            .set(iid!(), ui)
+                            .add(|| {
+                                Constrained::new()
+                                    .max(Size::new(20.0, 20.0))
+                                    .set(iid!(), ui)
+                                    .add(|| {
+                                        rect(iid!(Icon), ui);
+                                    });
+                            });
+                        Positioned::new()
+                            .top(-5.0)
+                            .right(-5.0)
+                            .width(10.0)
+                            .height(10.0)
+                            .set(iid!(), ui)
+                            .add(|| {
+                                rect(iid!(Badge), ui);
+                            });
+                        Positioned::new()
+                            .left(10.0)
+                            .right(10.0)
+                            .bottom(0.0)
+                            .set(iid!(), ui)
+                            .add(|| {
+                                Constrained::new().max_height(15.0).set(iid!(), ui).add(|| {
+                                    rect(iid!(Caption), ui);
+                                });
+                            });
+                    });
+            });
+    }
+}
+
+#[test]
+fn stack_positioned() {
+    let app = run::<Stacked>(Size::new(200.0, 100.0));
+
+    // Only the image determines the size of the stack
+    assert_eq!(bounds_of(&app, "Stack").size, Size::new(100.0, 60.0));
+    assert_eq!(bounds_of(&app, "Image").origin, Position::new(0.0, 0.0));
+    assert_eq!(bounds_of(&app, "Icon").origin, Position::new(40.0, 20.0));
+    assert_eq!(
+        bounds_of(&app, "Badge"),
+        Bounds::new(Position::new(95.0, -5.0), Size::new(10.0, 10.0))
+    );
+    assert_eq!(
+        bounds_of(&app, "Caption"),
+        Bounds::new(Position::new(10.0, 45.0), Size::new(80.0, 15.0))
+    );
+}
+
+#[derive(Default)]
+struct Form;
+
+impl Component for Form {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Grid::new()
+            .columns(&[Track::Auto, Track::Fraction(1.0), Track::Fixed(40.0)])
+            .rows(&[Track::Fixed(30.0)])
+            .gap(10.0)
+            .set(iid!(), ui)
+            .add(|| {
+                Constrained::new().min_width(60.0).set(iid!(), ui).add(|| {
+                    rect(iid!(Label), ui);
+                });
+                rect(iid!(Input), ui);
+                rect(iid!(Button), ui);
+                Constrained::new()
+                    .min_width(80.0)
+                    .max_height(25.0)
+                    .set(iid!(), ui)
+                    .add(|| {
+                        rect(iid!(LongLabel), ui);
+                    });
+                GridItem::new()
+                    .row(3)
+                    .column(1)
+                    .column_span(2)
+                    .set(iid!(), ui)
+                    .add(|| {
+                        rect(iid!(Wide), ui);
+                    });
+            });
+    }
+}
+
+#[test]
+fn grid_tracks() {
+    let app = run::<Form>(Size::new(300.0, 200.0));
+
+    // The auto column fits the wider label, the fraction takes the rest
+    assert_eq!(
+        bounds_of(&app, "Label"),
+        Bounds::new(Position::new(0.0, 0.0), Size::new(80.0, 30.0))
+    );
+    assert_eq!(
+        bounds_of(&app, "Input"),
+        Bounds::new(Position::new(90.0, 0.0), Size::new(160.0, 30.0))
+    );
+    assert_eq!(
+        bounds_of(&app, "Button"),
+        Bounds::new(Position::new(260.0, 0.0), Size::new(40.0, 30.0))
+    );
+    // The implicit auto rows fit their content, the empty one collapses to zero
+    assert_eq!(
+        bounds_of(&app, "LongLabel"),
+        Bounds::new(Position::new(0.0, 40.0), Size::new(80.0, 25.0))
+    );
+    assert_eq!(
+        bounds_of(&app, "Wide"),
+        Bounds::new(Position::new(90.0, 85.0), Size::new(210.0, 0.0))
+    );
+}
+
+#[derive(Default)]
+struct Placement;
+
+impl Component for Placement {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Grid::new()
+            .columns(&[Track::Fixed(50.0), Track::Fixed(50.0)])
+            .rows(&[Track::Fixed(20.0)])
+            .set(iid!(), ui)
+            .add(|| {
+                rect(iid!(First), ui);
+                Constrained::new().min_height(10.0).set(iid!(), ui).add(|| {
+                    rect(iid!(Second), ui);
+                });
+                GridItem::new().column(5).set(iid!(), ui).add(|| {
+                    rect(iid!(OutOfRange), ui);
+                });
+                GridItem::new()
+                    .row(1)
+                    .column(1)
+                    .column_span(3)
+                    .set(iid!(), ui)
+                    .add(|| {
+                        rect(iid!(TooWide), ui);
+                    });
+                GridItem::new().row(2).row_span(3).set(iid!(), ui).add(|| {
+                    Constrained::new().min_height(60.0).set(iid!(), ui).add(|| {
+                        rect(iid!(Tall), ui);
+                    });
+                });
+            });
+    }
+}
+
+#[test]
+fn grid_placement() {
+    let app = run::<Placement>(Size::new(300.0, 200.0));
+
+    // Items beyond the last column are moved into it
+    assert_eq!(
+        bounds_of(&app, "OutOfRange"),
+        Bounds::new(Position::new(50.0, 0.0), Size::new(50.0, 20.0))
+    );
+    assert_eq!(
+        bounds_of(&app, "TooWide"),
+        Bounds::new(Position::new(50.0, 20.0), Size::new(50.0, 10.0))
+    );
+    // Unplaced children skip the occupied cells
+    assert_eq!(bounds_of(&app, "First").origin, Position::new(0.0, 0.0));
+    assert_eq!(
+        bounds_of(&app, "Second"),
+        Bounds::new(Position::new(0.0, 20.0), Size::new(50.0, 10.0))
+    );
+    // The spanned rows beyond the defined ones share the height of their content
+    assert_eq!(
+        bounds_of(&app, "Tall"),
+        Bounds::new(Position::new(0.0, 30.0), Size::new(50.0, 60.0))
+    );
+}
@@ -0,0 +1,74 @@
+use coat::backend::headless::{assert_snapshot, Headless};
+use coat::backend::software::{assert_golden_image, SoftwareRenderer};
+use coat::*;
+
+fn snapshot_path(file: &str) -> String {
+    format!("{}/tests/snapshots/{}", env!("CARGO_MANIFEST_DIR"), file)
+}
+
+#[derive(Default)]
+struct App;
+
+impl Component for App {
+    type State = ();
+    type Msg = ();
+    type Event = ();
+
+    fn init(_props: &Self) -> Self::State {}
+
+    fn view(_props: &Self, _state: &Self::State, ui: &mut UiView<Self>) {
+        Padding::new().all(10.0).set(iid!(), ui).add(|| {
+            Linear::new()
+                .vertical()
+                .spacing(10.0)
+                .set(iid!(), ui)
+                .add(|| {
+                    for label in &["Hallo!", "Welt!"] {
+                        Constrained::new()
+                            .max_height(30.0)
+                            .set(iid!().key(label), ui)
+                            .add(|| {
+                                Stack::new().set(iid!().key(label), ui).add(|| {
+                                    Rectangle::new()
+                                        .color(Color::rgb(0.3, 0.3, 0.7))
+                                        .set(iid!().key(label), ui);
+
+                                    Text::new()
+                                        .content(label)
+                                        .size(14)
+                                        .set(iid!().key(label), ui);
+                                });
+                            });
+                    }
+
+                    Constrained::new()
+                        .max(Size::new(20.0, 20.0))
+                        .set(iid!(), ui)
+                        .add(|| {
+                            Clip::new().set(iid!(), ui).add(|| {
+                                Rectangle::new()
+                                    .color(Color::rgb(0.7, 0.3, 0.3))
+                                    .set(iid!(), ui);
+                            });
+                        });
+                });
+        });
+    }
+}
+
+#[test]
+fn buttons() {
+    let mut app: Headless<App> = Headless::new(Size::new(200.0, 150.0));
+    app.run();
+    assert_snapshot(
+        snapshot_path("buttons.snap"),
+        &app.display_list().snapshot(),
+    );
+}
+
+#[test]
+fn buttons_image() {
+    let mut app: Headless<App, SoftwareRenderer> = Headless::new(Size::new(200.0, 150.0));
+    app.run();
+    assert_golden_image(snapshot_path("buttons.png"), app.renderer());
+}